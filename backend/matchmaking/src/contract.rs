@@ -0,0 +1,473 @@
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+mod matching;
+mod state;
+
+use linera_sdk::{
+    abi::WithContractAbi,
+    linera_base_types::{AccountOwner, ApplicationId, ChainId},
+    views::{RootView, View},
+    Contract, ContractRuntime,
+};
+
+use crate::state::{Assignment, MatchedTable, MatchmakingState, PlayerRating, QueueEntry};
+use linot::{
+    LinotAbi, LinotResponse, MatchConfig, MatchStatus, Operation as LinotOperation, SeatReservation,
+};
+use linot_matchmaking::{
+    MatchmakingAbi, MatchmakingConfig, MatchmakingError, MatchmakingParameters, Message,
+    Operation,
+};
+
+pub struct MatchmakingContract {
+    state: MatchmakingState,
+    runtime: ContractRuntime<Self>,
+}
+
+linera_sdk::contract!(MatchmakingContract);
+
+impl WithContractAbi for MatchmakingContract {
+    type Abi = MatchmakingAbi;
+}
+
+impl Contract for MatchmakingContract {
+    type Message = Message;
+    type Parameters = MatchmakingParameters;
+    type InstantiationArgument = MatchmakingConfig;
+    type EventValue = ();
+
+    async fn load(runtime: ContractRuntime<Self>) -> Self {
+        let state = MatchmakingState::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load state");
+        MatchmakingContract { state, runtime }
+    }
+
+    async fn instantiate(&mut self, config: Self::InstantiationArgument) {
+        self.state.config.set(config);
+    }
+
+    async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
+        let result = match operation {
+            Operation::Enqueue {
+                nickname,
+                table_size,
+                ranked,
+            } => self.handle_enqueue_request(nickname, table_size, ranked).await,
+            Operation::LeaveQueue => self.handle_leave_request(),
+            Operation::RunMatcher => self.handle_run_matcher(),
+            Operation::CollectResults => self.handle_collect_results().await,
+        };
+
+        if let Err(e) = result {
+            panic!("Operation failed: {}", e);
+        }
+    }
+
+    async fn execute_message(&mut self, message: Self::Message) {
+        let result = match message {
+            Message::Enqueue {
+                owner,
+                nickname,
+                table_size,
+                ranked,
+            } => {
+                let chain_id = self
+                    .runtime
+                    .message_origin_chain_id()
+                    .expect("Incoming message must have an origin chain");
+                if self.runtime.authenticated_signer() != Some(owner) {
+                    Err(MatchmakingError::CallerRequired)
+                } else {
+                    self.handle_enqueue(owner, nickname, chain_id, table_size, ranked)
+                        .await
+                }
+            }
+            Message::LeaveQueue { owner } => {
+                if self.runtime.authenticated_signer() != Some(owner) {
+                    Err(MatchmakingError::CallerRequired)
+                } else {
+                    self.handle_leave(owner)
+                }
+            }
+            Message::MatchFound {
+                owner,
+                table,
+                seats,
+                ranked,
+            } => {
+                // Only the hub opens tables; anyone else could send a player
+                // to a table of their choosing
+                let hub = self.runtime.application_creator_chain_id();
+                if self.runtime.message_origin_chain_id() != Some(hub) {
+                    Err(MatchmakingError::NotFromHub)
+                } else {
+                    self.record_assignment(owner, table, seats, ranked);
+                    Ok(())
+                }
+            }
+        };
+
+        // A queue request that no longer applies (already queued, already
+        // matched) is dropped rather than failing the inbox; the player's
+        // chain keeps no pending request
+        let _ = result;
+    }
+
+    async fn store(mut self) {
+        self.state.save().await.expect("Failed to save state");
+    }
+}
+
+impl MatchmakingContract {
+    fn caller(&mut self) -> Result<AccountOwner, MatchmakingError> {
+        self.runtime
+            .authenticated_signer()
+            .ok_or(MatchmakingError::CallerRequired)
+    }
+
+    fn is_hub(&mut self) -> bool {
+        self.runtime.chain_id() == self.runtime.application_creator_chain_id()
+    }
+
+    /// Handle a player asking to queue, forwarding the request to the hub if needed
+    async fn handle_enqueue_request(&mut self, nickname: String, table_size: u8, ranked: bool) -> Result<(), MatchmakingError> {
+        let caller = self.caller()?;
+
+        // Validate early so a bad request never leaves the player's chain
+        if !(2..=matching::MAX_TABLE_SIZE).contains(&table_size) {
+            return Err(MatchmakingError::InvalidTableSize(table_size));
+        }
+        let nickname = linot::validate_nickname(&nickname)?;
+
+        if self.is_hub() {
+            let chain_id = self.runtime.chain_id();
+            return self
+                .handle_enqueue(caller, nickname, chain_id, table_size, ranked)
+                .await;
+        }
+
+        let hub = self.runtime.application_creator_chain_id();
+        self.runtime
+            .prepare_message(Message::Enqueue {
+                owner: caller,
+                nickname,
+                table_size,
+                ranked,
+            })
+            .with_authentication()
+            .send_to(hub);
+
+        Ok(())
+    }
+
+    /// Handle a player asking to leave the queue, forwarding the request to the hub if needed
+    fn handle_leave_request(&mut self) -> Result<(), MatchmakingError> {
+        let caller = self.caller()?;
+
+        if self.is_hub() {
+            return self.handle_leave(caller);
+        }
+
+        let hub = self.runtime.application_creator_chain_id();
+        self.runtime
+            .prepare_message(Message::LeaveQueue { owner: caller })
+            .with_authentication()
+            .send_to(hub);
+
+        Ok(())
+    }
+
+    /// Add a player to the hub's queue and try to seat them straight away
+    async fn handle_enqueue(&mut self, owner: AccountOwner, nickname: String, chain_id: ChainId, table_size: u8, ranked: bool) -> Result<(), MatchmakingError> {
+        let config = self.state.config.get().clone();
+        let mut queue = self.state.queue.get().clone();
+
+        // Validate: table size a Linot match supports
+        if !(2..=matching::MAX_TABLE_SIZE).contains(&table_size) {
+            return Err(MatchmakingError::InvalidTableSize(table_size));
+        }
+        let nickname = linot::validate_nickname(&nickname)?;
+
+        // Validate: one queue entry per player
+        if queue.iter().any(|entry| entry.owner == owner) {
+            return Err(MatchmakingError::AlreadyQueued);
+        }
+
+        let mut rating = self
+            .state
+            .ratings
+            .get(&owner)
+            .await
+            .expect("Failed to read rating")
+            .unwrap_or_else(|| PlayerRating {
+                owner,
+                nickname: String::new(),
+                rating: config.initial_rating,
+                games: 0,
+            });
+
+        // Ranked players appear on the ladder under their latest nickname
+        if ranked {
+            rating.nickname = nickname.clone();
+            self.state
+                .ratings
+                .insert(&owner, rating.clone())
+                .expect("Failed to store rating");
+        }
+
+        queue.push(QueueEntry {
+            owner,
+            nickname,
+            chain_id,
+            table_size,
+            ranked,
+            rating: rating.rating,
+            enqueued_at: self.runtime.system_time().micros(),
+        });
+        self.state.queue.set(queue);
+
+        self.handle_run_matcher()
+    }
+
+    /// Remove a player from the hub's queue
+    fn handle_leave(&mut self, owner: AccountOwner) -> Result<(), MatchmakingError> {
+        let mut queue = self.state.queue.get().clone();
+
+        let position = queue
+            .iter()
+            .position(|entry| entry.owner == owner)
+            .ok_or(MatchmakingError::NotQueued)?;
+        queue.remove(position);
+        self.state.queue.set(queue);
+
+        Ok(())
+    }
+
+    /// Handle grouping waiting players into tables and notifying their chains
+    ///
+    /// Anyone may call this; rating brackets widen with wait time, so calling
+    /// it again later can seat players who did not fit earlier.
+    fn handle_run_matcher(&mut self) -> Result<(), MatchmakingError> {
+        if !self.is_hub() {
+            return Err(MatchmakingError::NotHubChain);
+        }
+
+        let config = self.state.config.get().clone();
+        let queue = self.state.queue.get().clone();
+        let now = self.runtime.system_time().micros();
+
+        let groups = matching::form_tables(&queue, now, &config);
+        if groups.is_empty() {
+            return Ok(());
+        }
+
+        let MatchmakingParameters { linot_module } = self.runtime.application_parameters();
+        let mut tables = self.state.tables.get().clone();
+        for group in &groups {
+            let entries: Vec<&QueueEntry> = group.iter().map(|&i| &queue[i]).collect();
+            let ranked = entries[0].ranked;
+            let seats: Vec<AccountOwner> = entries.iter().map(|entry| entry.owner).collect();
+
+            // Private, with a seat held for each matched player so neither
+            // strangers nor bots can take it, started as soon as they are all
+            // seated and ready, and given up on if someone never turns up.
+            // This application hosts it, so no player can reset the table
+            let reserved_seats = seats
+                .iter()
+                .enumerate()
+                .map(|(seat, &owner)| SeatReservation {
+                    seat: seat as u8,
+                    owner,
+                })
+                .collect();
+            let match_config = MatchConfig {
+                max_players: seats.len() as u8,
+                is_ranked: ranked,
+                allowlist: seats.clone(),
+                reserved_seats,
+                auto_start: true,
+                lobby_expiry_secs: Some(config.table_lobby_secs),
+                host: Some(AccountOwner::from(self.runtime.application_id().forget_abi())),
+                ..MatchConfig::default()
+            };
+            let application_id: ApplicationId<LinotAbi> = self.runtime.create_application(
+                linot_module,
+                &(),
+                &match_config,
+                Vec::new(),
+            );
+            let table = application_id.forget_abi();
+
+            for entry in &entries {
+                self.notify_match_found(entry, table, &seats, ranked);
+            }
+            tables.push(MatchedTable {
+                application_id: table,
+                seats,
+                ranked,
+                created_at: now,
+                finished: false,
+            });
+        }
+
+        let seated: Vec<usize> = groups.into_iter().flatten().collect();
+        let remaining = queue
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !seated.contains(i))
+            .map(|(_, entry)| entry)
+            .collect();
+        self.state.queue.set(remaining);
+        self.state.tables.set(tables);
+
+        Ok(())
+    }
+
+    /// Handle reading finished tables and moving ratings for ranked ones
+    ///
+    /// Anyone may call this; each table is only rated once. A table whose
+    /// lobby expired before every matched player sat down is cancelled (if
+    /// nobody has yet) and scored as a forfeit, like a finished one.
+    async fn handle_collect_results(&mut self) -> Result<(), MatchmakingError> {
+        if !self.is_hub() {
+            return Err(MatchmakingError::NotHubChain);
+        }
+
+        let config = self.state.config.get().clone();
+        let mut tables = self.state.tables.get().clone();
+
+        for table in tables.iter_mut().filter(|table| !table.finished) {
+            let application_id = table.application_id.with_abi::<LinotAbi>();
+            let (placements, tied) = match self
+                .runtime
+                .call_application(false, application_id, &LinotOperation::GetResult)
+            {
+                LinotResponse::Result(Some(result)) if result.drawn => (result.placements, result.winners),
+                LinotResponse::Result(Some(result)) => (result.placements, Vec::new()),
+                _ => match self.read_forfeit(application_id) {
+                    Some(order) => (order, Vec::new()),
+                    None => continue,
+                },
+            };
+            table.finished = true;
+            if !table.ranked {
+                continue;
+            }
+
+            // Finishing order, with the players sharing a draw first and seats
+            // that never joined placed last
+            let tied: Vec<AccountOwner> = tied
+                .into_iter()
+                .filter(|owner| table.seats.contains(owner))
+                .collect();
+            let mut order = tied.clone();
+            order.extend(
+                placements
+                    .into_iter()
+                    .filter(|owner| table.seats.contains(owner) && !tied.contains(owner)),
+            );
+            let missing: Vec<AccountOwner> = table
+                .seats
+                .iter()
+                .filter(|owner| !order.contains(owner))
+                .copied()
+                .collect();
+            order.extend(missing);
+
+            let mut ratings = Vec::with_capacity(order.len());
+            for owner in &order {
+                let rating = self
+                    .state
+                    .ratings
+                    .get(owner)
+                    .await
+                    .expect("Failed to read rating")
+                    .unwrap_or_else(|| PlayerRating {
+                        owner: *owner,
+                        nickname: String::new(),
+                        rating: config.initial_rating,
+                        games: 0,
+                    });
+                ratings.push(rating);
+            }
+
+            let current: Vec<u32> = ratings.iter().map(|rating| rating.rating).collect();
+            let changes = matching::rating_changes(&current, tied.len(), config.k_factor);
+            for (mut rating, change) in ratings.into_iter().zip(changes) {
+                rating.rating = rating.rating.saturating_add_signed(change);
+                rating.games += 1;
+                let owner = rating.owner;
+                self.state
+                    .ratings
+                    .insert(&owner, rating)
+                    .expect("Failed to store rating");
+            }
+        }
+
+        self.state.tables.set(tables);
+
+        Ok(())
+    }
+
+    /// Forfeit order of a cancelled table, or of one whose lobby has expired
+    ///
+    /// Players who sat down at a table that never started rank ahead of the
+    /// no-shows, in seat order. A match cancelled after it started ranks
+    /// anyone who went out first, then the leavers with the last to leave ahead.
+    fn read_forfeit(&mut self, application_id: ApplicationId<LinotAbi>) -> Option<Vec<AccountOwner>> {
+        let LinotResponse::PublicState(public) =
+            self.runtime
+                .call_application(false, application_id, &LinotOperation::GetPublicState)
+        else {
+            return None;
+        };
+
+        let now = self.runtime.system_time().micros();
+        match public.status {
+            MatchStatus::Waiting if public.lobby_expires_at.is_some_and(|deadline| now >= deadline) => {
+                let expire = LinotOperation::ExpireLobby { expected_version: None };
+                self.runtime.call_application(false, application_id, &expire);
+            }
+            MatchStatus::Cancelled if !public.left_order.is_empty() => {
+                let mut order = public.placements.clone();
+                order.extend(public.left_order.iter().rev());
+                return Some(order);
+            }
+            MatchStatus::Cancelled => {}
+            _ => return None,
+        }
+
+        Some(public.players.iter().map(|seat| seat.owner).collect())
+    }
+
+    /// Tell a queued player's chain where they were seated
+    fn notify_match_found(&mut self, entry: &QueueEntry, table: ApplicationId, seats: &[AccountOwner], ranked: bool) {
+        if entry.chain_id == self.runtime.chain_id() {
+            self.record_assignment(entry.owner, table, seats.to_vec(), ranked);
+            return;
+        }
+
+        self.runtime.send_message(
+            entry.chain_id,
+            Message::MatchFound {
+                owner: entry.owner,
+                table,
+                seats: seats.to_vec(),
+                ranked,
+            },
+        );
+    }
+
+    /// Record a table found for one of this chain's players
+    fn record_assignment(&mut self, owner: AccountOwner, table: ApplicationId, seats: Vec<AccountOwner>, ranked: bool) {
+        let mut assignments = self.state.assignments.get().clone();
+        assignments.push(Assignment {
+            owner,
+            table,
+            seats,
+            ranked,
+        });
+        self.state.assignments.set(assignments);
+    }
+}
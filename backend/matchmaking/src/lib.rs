@@ -0,0 +1,136 @@
+use async_graphql::{Request, Response};
+use linera_sdk::{
+    abi::{ContractAbi, ServiceAbi},
+    linera_base_types::{AccountOwner, ApplicationId, ModuleId},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// ============ Error Types ============
+
+#[derive(Debug, Error)]
+pub enum MatchmakingError {
+    #[error("Already in the queue")]
+    AlreadyQueued,
+
+    #[error("Not in the queue")]
+    NotQueued,
+
+    #[error("Tables must seat 2 to 6 players, got {0}")]
+    InvalidTableSize(u8),
+
+    #[error("Invalid nickname: {0}")]
+    InvalidNickname(#[from] linot::NicknameError),
+
+    #[error("Only the hub chain runs the matcher")]
+    NotHubChain,
+
+    #[error("Caller authentication required")]
+    CallerRequired,
+
+    #[error("Table assignments are only accepted from the hub chain")]
+    NotFromHub,
+}
+
+// ============ ABI Definition ============
+
+pub struct MatchmakingAbi;
+
+impl ContractAbi for MatchmakingAbi {
+    type Operation = Operation;
+    type Response = ();
+}
+
+impl ServiceAbi for MatchmakingAbi {
+    type Query = Request;
+    type QueryResponse = Response;
+}
+
+/// Application parameters, fixed when the matchmaking application is created
+///
+/// The chain that creates the application is the hub: it holds the queue,
+/// the ratings and every table the matcher opens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchmakingParameters {
+    /// Published Linot module used to instantiate one match table per group
+    pub linot_module: ModuleId,
+}
+
+// ============ Matchmaking Configuration ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct MatchmakingConfig {
+    /// Largest rating gap allowed at a ranked table for a player who just queued
+    pub base_spread: u32,
+    /// How much that gap widens for every minute the oldest player has waited
+    pub spread_per_minute: u32,
+    /// Rating given to players on their first ranked match
+    pub initial_rating: u32,
+    /// Elo K-factor: the most a single match can move a rating
+    pub k_factor: u32,
+    /// How long a matched table waits for its players; players still missing
+    /// then forfeit and place behind everyone who took their seat
+    #[serde(default = "default_table_lobby_secs")]
+    pub table_lobby_secs: u64,
+}
+
+fn default_table_lobby_secs() -> u64 {
+    300
+}
+
+impl Default for MatchmakingConfig {
+    fn default() -> Self {
+        Self {
+            base_spread: 100,
+            spread_per_minute: 50,
+            initial_rating: 1200,
+            k_factor: 32,
+            table_lobby_secs: default_table_lobby_secs(),
+        }
+    }
+}
+
+// ============ Operations (GraphQL Mutations) ============
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Operation {
+    /// Join the queue for a table of `table_size` players
+    Enqueue {
+        nickname: String,
+        table_size: u8,
+        ranked: bool,
+    },
+
+    /// Leave the queue before being matched
+    LeaveQueue,
+
+    /// Group waiting players into tables (hub chain only; anyone may trigger it)
+    RunMatcher,
+
+    /// Read finished tables, close abandoned ones and update ratings (hub chain only)
+    CollectResults,
+}
+
+// ============ Messages (Cross-Chain Communication) ============
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Message {
+    /// A player's chain forwards a queue request to the hub
+    Enqueue {
+        owner: AccountOwner,
+        nickname: String,
+        table_size: u8,
+        ranked: bool,
+    },
+
+    /// A player's chain withdraws a queue request
+    LeaveQueue { owner: AccountOwner },
+
+    /// The hub tells a player's chain which table they were seated at
+    MatchFound {
+        owner: AccountOwner,
+        table: ApplicationId,
+        seats: Vec<AccountOwner>,
+        ranked: bool,
+    },
+}
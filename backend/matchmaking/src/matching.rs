@@ -0,0 +1,229 @@
+//! Queue matching and rating updates
+//!
+//! Pure functions used by the contract to group waiting players into tables
+//! and to move ratings once a ranked table finishes.
+
+use crate::state::QueueEntry;
+use linot_matchmaking::MatchmakingConfig;
+
+/// Largest table a Linot match supports
+pub const MAX_TABLE_SIZE: u8 = 6;
+
+/// Microseconds in one minute of queue time
+const MICROS_PER_MINUTE: u64 = 60_000_000;
+
+/// Largest rating gap `entry` accepts from a table-mate after waiting until `now`
+pub fn allowed_spread(entry: &QueueEntry, now: u64, config: &MatchmakingConfig) -> u32 {
+    let minutes = now.saturating_sub(entry.enqueued_at) / MICROS_PER_MINUTE;
+    let widening = u64::from(config.spread_per_minute).saturating_mul(minutes);
+    u64::from(config.base_spread)
+        .saturating_add(widening)
+        .min(u64::from(u32::MAX)) as u32
+}
+
+/// Group queued players into tables, returning queue indices per table
+///
+/// Players are served oldest first. Each one is seated with the players
+/// closest in rating who want the same table size and ranked flag; at a
+/// ranked table every pair of players sits within the oldest player's
+/// allowed spread. Unranked tables ignore ratings and simply take the
+/// longest waiters. Players that cannot fill a table yet stay in the queue.
+pub fn form_tables(queue: &[QueueEntry], now: u64, config: &MatchmakingConfig) -> Vec<Vec<usize>> {
+    let mut taken = vec![false; queue.len()];
+    let mut tables = Vec::new();
+
+    for anchor in 0..queue.len() {
+        if taken[anchor] {
+            continue;
+        }
+        let entry = &queue[anchor];
+        let spread = allowed_spread(entry, now, config);
+
+        let mut candidates: Vec<usize> = (0..queue.len())
+            .filter(|&i| i != anchor && !taken[i])
+            .filter(|&i| queue[i].table_size == entry.table_size && queue[i].ranked == entry.ranked)
+            .filter(|&i| !entry.ranked || queue[i].rating.abs_diff(entry.rating) <= spread)
+            .collect();
+        if entry.ranked {
+            candidates.sort_by_key(|&i| (queue[i].rating.abs_diff(entry.rating), i));
+        }
+
+        // Closest first, skipping anyone too far from a player already seated
+        let size = usize::from(entry.table_size);
+        let mut seats = vec![anchor];
+        for &candidate in &candidates {
+            if seats.len() == size {
+                break;
+            }
+            let rating = queue[candidate].rating;
+            if !entry.ranked || seats.iter().all(|&seat| queue[seat].rating.abs_diff(rating) <= spread) {
+                seats.push(candidate);
+            }
+        }
+        if seats.len() < size {
+            continue;
+        }
+        seats.sort_unstable();
+        for &seat in &seats {
+            taken[seat] = true;
+        }
+        tables.push(seats);
+    }
+
+    tables
+}
+
+/// Elo rating changes for a finished table, given ratings in finishing order
+///
+/// Every pair of players is scored as a one-on-one game: the higher placed
+/// player wins, except that the first `tied` players (those sharing a drawn
+/// match, 0 when nobody drew) draw with each other. Each player's changes are
+/// averaged over their opponents, so a table moves ratings about as much as a
+/// single duel.
+pub fn rating_changes(ratings: &[u32], tied: usize, k_factor: u32) -> Vec<i32> {
+    let players = ratings.len();
+    if players < 2 {
+        return vec![0; players];
+    }
+
+    let weight = f64::from(k_factor) / (players - 1) as f64;
+    let mut deltas = vec![0.0_f64; players];
+    for ahead in 0..players {
+        for behind in ahead + 1..players {
+            let gap = f64::from(ratings[behind]) - f64::from(ratings[ahead]);
+            let expected = 1.0 / (1.0 + 10_f64.powf(gap / 400.0));
+            let score = if behind < tied { 0.5 } else { 1.0 };
+            let change = weight * (score - expected);
+            deltas[ahead] += change;
+            deltas[behind] -= change;
+        }
+    }
+
+    deltas.iter().map(|delta| delta.round() as i32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+
+    fn entry(seat: u8, rating: u32, table_size: u8, ranked: bool, enqueued_at: u64) -> QueueEntry {
+        QueueEntry {
+            owner: AccountOwner::Reserved(seat),
+            nickname: format!("player{}", seat),
+            chain_id: "0".repeat(64).parse::<ChainId>().unwrap(),
+            table_size,
+            ranked,
+            rating,
+            enqueued_at,
+        }
+    }
+
+    #[test]
+    fn decisive_result_moves_winner_up_and_loser_down() {
+        let changes = rating_changes(&[1200, 1200], 0, 32);
+        assert_eq!(changes, vec![16, -16]);
+    }
+
+    #[test]
+    fn upset_moves_ratings_further_than_expected_win() {
+        let upset = rating_changes(&[1000, 1400], 0, 32);
+        let expected = rating_changes(&[1400, 1000], 0, 32);
+        assert!(upset[0] > expected[0]);
+        assert_eq!(upset.iter().sum::<i32>(), 0);
+    }
+
+    #[test]
+    fn draw_between_equal_players_changes_nothing() {
+        assert_eq!(rating_changes(&[1300, 1300], 2, 32), vec![0, 0]);
+    }
+
+    #[test]
+    fn only_tied_players_draw_with_each_other() {
+        // Two players share the draw, the third lost to both of them
+        let changes = rating_changes(&[1200, 1200, 1200], 2, 32);
+        assert_eq!(changes[0], changes[1]);
+        assert!(changes[0] > 0);
+        assert!(changes[2] < 0);
+
+        // Scoring every pair as a draw would have moved nobody
+        let everyone_drew = rating_changes(&[1200, 1200, 1200], 3, 32);
+        assert_eq!(everyone_drew, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn single_player_is_not_rated() {
+        assert_eq!(rating_changes(&[1500], 0, 32), vec![0]);
+    }
+
+    #[test]
+    fn unranked_tables_take_the_longest_waiters() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![
+            entry(0, 900, 2, false, 0),
+            entry(1, 2000, 2, false, 1),
+            entry(2, 1200, 2, false, 2),
+        ];
+        assert_eq!(form_tables(&queue, 3, &config), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn tables_only_group_same_size_and_ranked_flag() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![
+            entry(0, 1200, 2, true, 0),
+            entry(1, 1200, 3, true, 0),
+            entry(2, 1200, 2, false, 0),
+            entry(3, 1210, 2, true, 0),
+        ];
+        assert_eq!(form_tables(&queue, 0, &config), vec![vec![0, 3]]);
+    }
+
+    #[test]
+    fn ranked_tables_pick_closest_ratings_within_spread() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![
+            entry(0, 1200, 2, true, 0),
+            entry(1, 1290, 2, true, 0),
+            entry(2, 1220, 2, true, 0),
+        ];
+        assert_eq!(form_tables(&queue, 0, &config), vec![vec![0, 2]]);
+    }
+
+    #[test]
+    fn ranked_spread_widens_with_waiting_time() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![entry(0, 1200, 2, true, 0), entry(1, 1400, 2, true, 0)];
+        assert!(form_tables(&queue, 0, &config).is_empty());
+
+        // 100 base + 50 per minute covers a 200 point gap after two minutes
+        let two_minutes = 2 * MICROS_PER_MINUTE;
+        assert_eq!(form_tables(&queue, two_minutes, &config), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn ranked_tables_keep_every_pair_within_spread() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![
+            entry(0, 1200, 3, true, 0),
+            entry(1, 1100, 3, true, 0),
+            entry(2, 1300, 3, true, 0),
+        ];
+        assert!(form_tables(&queue, 0, &config).is_empty());
+
+        let queue = vec![
+            entry(0, 1200, 3, true, 0),
+            entry(1, 1100, 3, true, 0),
+            entry(2, 1300, 3, true, 0),
+            entry(3, 1150, 3, true, 0),
+        ];
+        assert_eq!(form_tables(&queue, 0, &config), vec![vec![0, 1, 3]]);
+    }
+
+    #[test]
+    fn incomplete_tables_stay_queued() {
+        let config = MatchmakingConfig::default();
+        let queue = vec![entry(0, 1200, 4, false, 0), entry(1, 1200, 4, false, 0)];
+        assert!(form_tables(&queue, 0, &config).is_empty());
+    }
+}
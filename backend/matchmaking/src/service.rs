@@ -0,0 +1,172 @@
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+#[allow(dead_code)] // Shared with the contract; the service only previews wait brackets
+mod matching;
+mod state;
+
+use async_graphql::{Context, EmptySubscription, Object, Request, Response, Schema};
+use linera_sdk::{
+    abi::WithServiceAbi,
+    linera_base_types::AccountOwner,
+    views::View,
+    Service, ServiceRuntime,
+};
+use std::sync::Arc;
+
+use crate::state::{Assignment, MatchedTable, MatchmakingState, PlayerRating, QueueEntry};
+use linot_matchmaking::{MatchmakingAbi, MatchmakingConfig, MatchmakingParameters, Operation};
+
+/// GraphQL service for querying the matchmaking queue and ladder
+pub struct MatchmakingService {
+    state: Arc<MatchmakingState>,
+    runtime: Arc<ServiceRuntime<Self>>,
+}
+
+linera_sdk::service!(MatchmakingService);
+
+impl WithServiceAbi for MatchmakingService {
+    type Abi = MatchmakingAbi;
+}
+
+impl Service for MatchmakingService {
+    type Parameters = MatchmakingParameters;
+
+    async fn new(runtime: ServiceRuntime<Self>) -> Self {
+        let state = MatchmakingState::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load state");
+        MatchmakingService {
+            state: Arc::new(state),
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    async fn handle_query(&self, request: Request) -> Response {
+        let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .data(self.state.clone())
+            .data(self.runtime.clone())
+            .finish();
+
+        schema.execute(request).await
+    }
+}
+
+/// GraphQL query root
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Get the matchmaking configuration
+    async fn config(&self, ctx: &Context<'_>) -> MatchmakingConfig {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        state.config.get().clone()
+    }
+
+    /// Get the players waiting on the hub, oldest first
+    async fn queue(&self, ctx: &Context<'_>) -> Vec<QueueEntry> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        state.queue.get().clone()
+    }
+
+    /// Get the rating gap a queued player currently accepts (None if not queued)
+    async fn allowed_spread(&self, ctx: &Context<'_>, owner: AccountOwner) -> Option<u32> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<MatchmakingService>>>();
+        let now = runtime.system_time().micros();
+        let config = state.config.get();
+        state
+            .queue
+            .get()
+            .iter()
+            .find(|entry| entry.owner == owner)
+            .map(|entry| matching::allowed_spread(entry, now, config))
+    }
+
+    /// Get a player's ranked rating (None before their first ranked queue)
+    async fn rating(&self, ctx: &Context<'_>, owner: AccountOwner) -> Option<PlayerRating> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        state.ratings.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the ranked ladder, best rating first
+    async fn ladder(&self, ctx: &Context<'_>, limit: Option<usize>) -> Vec<PlayerRating> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        let mut ladder = Vec::new();
+        state
+            .ratings
+            .for_each_index_value(|_owner, rating| {
+                ladder.push(rating.into_owned());
+                Ok(())
+            })
+            .await
+            .expect("Failed to read ratings");
+        ladder.sort_by_key(|rating| std::cmp::Reverse((rating.rating, rating.games)));
+        ladder.truncate(limit.unwrap_or(ladder.len()));
+        ladder
+    }
+
+    /// Get the tables opened by the matcher
+    async fn tables(&self, ctx: &Context<'_>, unfinished_only: Option<bool>) -> Vec<MatchedTable> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        let unfinished_only = unfinished_only.unwrap_or(false);
+        state
+            .tables
+            .get()
+            .iter()
+            .filter(|table| !unfinished_only || !table.finished)
+            .cloned()
+            .collect()
+    }
+
+    /// Get the most recent table found for a player of this chain, if any
+    async fn my_match(&self, ctx: &Context<'_>, owner: AccountOwner) -> Option<Assignment> {
+        let state = ctx.data_unchecked::<Arc<MatchmakingState>>();
+        state
+            .assignments
+            .get()
+            .iter()
+            .rev()
+            .find(|assignment| assignment.owner == owner)
+            .cloned()
+    }
+}
+
+// ============ GraphQL Mutation Root ============
+
+/// GraphQL mutation root for matchmaking actions
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Queue for a table of `table_size` players
+    async fn enqueue(&self, ctx: &Context<'_>, nickname: String, table_size: u8, ranked: bool) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<MatchmakingService>>>();
+        runtime.schedule_operation(&Operation::Enqueue {
+            nickname,
+            table_size,
+            ranked,
+        });
+        true
+    }
+
+    /// Leave the queue
+    async fn leave_queue(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<MatchmakingService>>>();
+        runtime.schedule_operation(&Operation::LeaveQueue);
+        true
+    }
+
+    /// Seat waiting players whose brackets now overlap (hub chain)
+    async fn run_matcher(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<MatchmakingService>>>();
+        runtime.schedule_operation(&Operation::RunMatcher);
+        true
+    }
+
+    /// Rate finished ranked tables (hub chain)
+    async fn collect_results(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<MatchmakingService>>>();
+        runtime.schedule_operation(&Operation::CollectResults);
+        true
+    }
+}
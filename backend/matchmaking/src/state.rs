@@ -0,0 +1,88 @@
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ApplicationId, ChainId},
+    views::{MapView, RegisterView, RootView, ViewStorageContext},
+};
+use serde::{Deserialize, Serialize};
+
+use linot_matchmaking::MatchmakingConfig;
+
+/// Root application state stored on-chain using Linera Views
+///
+/// The hub chain uses the queue, ratings and tables; every other chain only
+/// records the tables its players were seated at.
+#[derive(RootView)]
+#[view(context = ViewStorageContext)]
+pub struct MatchmakingState {
+    /// Matchmaking configuration (set at instantiation)
+    pub config: RegisterView<MatchmakingConfig>,
+    /// Players waiting for a table, oldest first
+    pub queue: RegisterView<Vec<QueueEntry>>,
+    /// Ranked rating of every player who has queued for a ranked table
+    pub ratings: MapView<AccountOwner, PlayerRating>,
+    /// Tables opened by the matcher
+    pub tables: RegisterView<Vec<MatchedTable>>,
+    /// Tables this chain's players were seated at, most recent last
+    pub assignments: RegisterView<Vec<Assignment>>,
+}
+
+// ============ Queue ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct QueueEntry {
+    /// Owner account
+    pub owner: AccountOwner,
+    /// Display nickname
+    pub nickname: String,
+    /// Chain to notify once a table is found
+    pub chain_id: ChainId,
+    /// Preferred number of players at the table
+    pub table_size: u8,
+    /// Whether the player wants a ranked table
+    pub ranked: bool,
+    /// Rating when the player queued
+    pub rating: u32,
+    /// When the player queued (microseconds)
+    pub enqueued_at: u64,
+}
+
+// ============ Ratings ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct PlayerRating {
+    /// Owner account
+    pub owner: AccountOwner,
+    /// Last nickname the player queued with
+    pub nickname: String,
+    /// Current Elo rating
+    pub rating: u32,
+    /// Ranked matches rated so far
+    pub games: u32,
+}
+
+// ============ Tables ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct MatchedTable {
+    /// Linot application instance hosting the match
+    pub application_id: ApplicationId,
+    /// Players seated at the table, in queue order
+    pub seats: Vec<AccountOwner>,
+    /// Whether the match counts towards ratings
+    pub ranked: bool,
+    /// When the matcher opened the table (microseconds)
+    pub created_at: u64,
+    /// The match finished and its result was collected
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct Assignment {
+    /// Player the table was found for
+    pub owner: AccountOwner,
+    /// Linot application instance hosting the match
+    pub table: ApplicationId,
+    /// Everyone seated at the table
+    pub seats: Vec<AccountOwner>,
+    /// Whether the match counts towards ratings
+    pub ranked: bool,
+}
@@ -0,0 +1,443 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::game_engine::{GameEngine, SpecialEffect};
+use crate::state::MatchData;
+use linot::{BotDifficulty, Card, CardSuit, CardValue};
+
+/// A move chosen for a bot seat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotMove {
+    /// Play a card from the bot's hand
+    Play {
+        card_id: u32,
+        chosen_suit: Option<CardSuit>,
+    },
+    /// Draw from the market
+    Draw,
+}
+
+/// What a seat can see when choosing a move: its own hand and the public table
+pub struct SeatView<'a> {
+    /// Seat index of the player choosing
+    pub seat: usize,
+    /// The player's own cards
+    pub hand: &'a [Card],
+    /// Top card of the discard pile
+    pub top_card: &'a Card,
+    /// Suit demanded by a Whot card, if any
+    pub active_demand: Option<CardSuit>,
+    /// Penalty cards the player must draw unless they defend
+    pub pending_penalty: u8,
+    /// Whether the rules let the player defend the pending penalty
+    pub may_defend: bool,
+    /// Card counts of the other players still in the rotation, by seat
+    pub opponent_card_counts: Vec<(usize, usize)>,
+    /// Whether going out only takes the next place (play-out matches)
+    pub play_out: bool,
+}
+
+impl<'a> SeatView<'a> {
+    /// Build the view of `seat`, or `None` before the first discard
+    pub fn new(state: &'a MatchData, seat: usize) -> Option<Self> {
+        Some(Self {
+            seat,
+            hand: &state.players.get(seat)?.cards,
+            top_card: state.discard_pile.last()?,
+            active_demand: state.active_shape_demand,
+            pending_penalty: state.pending_penalty,
+            may_defend: GameEngine::may_defend(state, seat),
+            opponent_card_counts: state
+                .players
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != seat && GameEngine::in_rotation(state, i))
+                .map(|(i, p)| (i, p.card_count))
+                .collect(),
+            play_out: state.play_out,
+        })
+    }
+
+    /// Cards in hand that `GameEngine::play_card` would accept right now
+    pub fn legal_cards(&self) -> Vec<&'a Card> {
+        if self.pending_penalty > 0 && !self.may_defend {
+            return Vec::new();
+        }
+        self.hand
+            .iter()
+            .filter(|card| {
+                GameEngine::is_valid_play(
+                    card,
+                    self.top_card,
+                    self.active_demand,
+                    self.pending_penalty,
+                )
+            })
+            .collect()
+    }
+}
+
+/// A playing policy: which legal card to play (or draw), and which suit to
+/// demand after a Whot card
+///
+/// Strategies must be deterministic so that every validator agrees on bot moves.
+pub trait Strategy {
+    /// Short identifier used by the simulator
+    #[allow(dead_code)] // Used in simulator.rs
+    fn name(&self) -> &'static str;
+
+    /// Pick one of the `legal` cards to play, or `None` to draw
+    fn choose_card<'a>(&self, view: &SeatView<'a>, legal: &[&'a Card]) -> Option<&'a Card>;
+
+    /// Pick the suit to demand after playing the Whot card `played`
+    fn choose_suit(&self, view: &SeatView, played: &Card) -> CardSuit {
+        most_common_suit(view.hand, played.id)
+    }
+}
+
+/// Ask a strategy for its move
+pub fn decide(strategy: &dyn Strategy, view: &SeatView) -> BotMove {
+    let legal = view.legal_cards();
+    match strategy.choose_card(view, &legal) {
+        Some(card) => BotMove::Play {
+            card_id: card.id,
+            chosen_suit: (card.value == CardValue::Whot)
+                .then(|| strategy.choose_suit(view, card)),
+        },
+        None => BotMove::Draw,
+    }
+}
+
+/// Strategy played by a bot seat of the given difficulty
+pub fn strategy_for(difficulty: BotDifficulty) -> &'static dyn Strategy {
+    match difficulty {
+        BotDifficulty::Easy => &FirstLegal,
+        BotDifficulty::Medium => &ShedHighest,
+        BotDifficulty::Hard => &Tactician,
+    }
+}
+
+/// All reference strategies
+#[allow(dead_code)] // Used in simulator.rs
+pub fn reference_strategies() -> [&'static dyn Strategy; 5] {
+    [&FirstLegal, &RandomLegal, &ShedHighest, &Tactician, &Advisor]
+}
+
+/// Choose the next move for the bot at `seat`
+#[allow(dead_code)] // Used in contract.rs
+pub fn choose_move(state: &MatchData, seat: usize, difficulty: BotDifficulty) -> BotMove {
+    match SeatView::new(state, seat) {
+        Some(view) => decide(strategy_for(difficulty), &view),
+        None => BotMove::Draw,
+    }
+}
+
+/// Move dictated by a human player's standing orders, if any applies
+///
+/// Auto-defend answers a pending Pick Two/Three with a matching card;
+/// auto-draw draws when no card in hand is legal.
+#[allow(dead_code)] // Used in contract.rs
+pub fn standing_order_move(state: &MatchData, seat: usize) -> Option<BotMove> {
+    let orders = state.players.get(seat)?.auto_actions;
+    let view = SeatView::new(state, seat)?;
+    let legal = view.legal_cards();
+
+    if orders.auto_defend && view.pending_penalty > 0 {
+        if let Some(defence) = legal.iter().find(|card| is_defence(card)) {
+            return Some(BotMove::Play {
+                card_id: defence.id,
+                chosen_suit: None,
+            });
+        }
+    }
+
+    if orders.auto_draw && legal.is_empty() {
+        return Some(BotMove::Draw);
+    }
+
+    None
+}
+
+// ============ Reference Strategies ============
+
+/// Plays the first legal card in hand order (easy bots)
+pub struct FirstLegal;
+
+impl Strategy for FirstLegal {
+    fn name(&self) -> &'static str {
+        "first-legal"
+    }
+
+    fn choose_card<'a>(&self, _view: &SeatView<'a>, legal: &[&'a Card]) -> Option<&'a Card> {
+        legal.first().copied()
+    }
+
+    fn choose_suit(&self, view: &SeatView, played: &Card) -> CardSuit {
+        view.hand
+            .iter()
+            .find(|card| card.id != played.id && card.value != CardValue::Whot)
+            .map(|card| card.suit)
+            .unwrap_or(CardSuit::Circle)
+    }
+}
+
+/// Plays a pseudo-random legal card, seeded by the visible position
+pub struct RandomLegal;
+
+impl Strategy for RandomLegal {
+    fn name(&self) -> &'static str {
+        "random-legal"
+    }
+
+    fn choose_card<'a>(&self, view: &SeatView<'a>, legal: &[&'a Card]) -> Option<&'a Card> {
+        if legal.is_empty() {
+            return None;
+        }
+        let mut hasher = DefaultHasher::new();
+        view.seat.hash(&mut hasher);
+        view.top_card.id.hash(&mut hasher);
+        for card in view.hand {
+            card.id.hash(&mut hasher);
+        }
+        Some(legal[hasher.finish() as usize % legal.len()])
+    }
+}
+
+/// Sheds the highest-value card, keeping Whot as a last resort (medium bots)
+pub struct ShedHighest;
+
+impl Strategy for ShedHighest {
+    fn name(&self) -> &'static str {
+        "shed-highest"
+    }
+
+    fn choose_card<'a>(&self, _view: &SeatView<'a>, legal: &[&'a Card]) -> Option<&'a Card> {
+        shed_highest(legal)
+    }
+}
+
+/// Attacks players close to winning, otherwise sheds high cards while holding
+/// back a penalty card for defence; demands the suit worth the most points
+/// in hand (hard bots)
+pub struct Tactician;
+
+impl Strategy for Tactician {
+    fn name(&self) -> &'static str {
+        "tactician"
+    }
+
+    fn choose_card<'a>(&self, view: &SeatView<'a>, legal: &[&'a Card]) -> Option<&'a Card> {
+        // Facing a penalty: any legal card is a defence
+        if view.pending_penalty > 0 {
+            return legal.first().copied();
+        }
+
+        let opponent_close = view.opponent_card_counts.iter().any(|&(_, count)| count <= 2);
+        if opponent_close {
+            let attack = legal.iter().find(|card| {
+                matches!(
+                    GameEngine::get_card_effect(card),
+                    SpecialEffect::DrawTwo
+                        | SpecialEffect::DrawThree
+                        | SpecialEffect::SkipNext
+                        | SpecialEffect::AllDrawOne
+                )
+            });
+            if let Some(card) = attack {
+                return Some(card);
+            }
+        }
+
+        let spare: Vec<&Card> = legal
+            .iter()
+            .filter(|card| !is_defence(card))
+            .copied()
+            .collect();
+
+        shed_highest(&spare).or_else(|| shed_highest(legal))
+    }
+
+    fn choose_suit(&self, view: &SeatView, played: &Card) -> CardSuit {
+        SUITS
+            .into_iter()
+            .max_by_key(|&suit| {
+                view.hand
+                    .iter()
+                    .filter(|card| card.id != played.id && card.suit == suit)
+                    .filter(|card| card.value != CardValue::Whot)
+                    .map(GameEngine::card_points)
+                    .sum::<u32>()
+            })
+            .unwrap_or(CardSuit::Circle)
+    }
+}
+
+/// Plays the top-ranked move from `evaluate_moves`, as suggested to players
+pub struct Advisor;
+
+impl Strategy for Advisor {
+    fn name(&self) -> &'static str {
+        "advisor"
+    }
+
+    fn choose_card<'a>(&self, view: &SeatView<'a>, _legal: &[&'a Card]) -> Option<&'a Card> {
+        evaluate_moves(view).first().map(|evaluation| evaluation.card)
+    }
+
+    fn choose_suit(&self, view: &SeatView, played: &Card) -> CardSuit {
+        Tactician.choose_suit(view, played)
+    }
+}
+
+// ============ Move Evaluation ============
+
+/// A legal play scored by the hint heuristics
+pub struct MoveEvaluation<'a> {
+    /// Card to play
+    pub card: &'a Card,
+    /// Suit to demand, if the card is a Whot
+    #[allow(dead_code)] // Used in service.rs
+    pub chosen_suit: Option<CardSuit>,
+    /// Higher is better
+    pub score: i32,
+    /// Why the move scored as it did
+    #[allow(dead_code)] // Used in service.rs
+    pub reasons: Vec<String>,
+}
+
+/// Rank every legal play for the seat, best first
+///
+/// Heuristics: win when possible, defend penalties, hold Whot for when you
+/// are stuck, dump high-value cards, keep a Pick Two or Pick Three back as a
+/// defence, and attack opponents who are close to winning.
+pub fn evaluate_moves<'a>(view: &SeatView<'a>) -> Vec<MoveEvaluation<'a>> {
+    let legal = view.legal_cards();
+    let closest_opponent = view
+        .opponent_card_counts
+        .iter()
+        .map(|&(_, count)| count)
+        .min();
+    let opponent_close = closest_opponent.is_some_and(|count| count <= 2);
+    let defences_held = view.hand.iter().filter(|card| is_defence(card)).count();
+
+    let mut evaluations: Vec<MoveEvaluation> = legal
+        .iter()
+        .map(|&card| {
+            let effect = GameEngine::get_card_effect(card);
+            let points = GameEngine::card_points(card);
+            let mut score = 0;
+            let mut reasons = Vec::new();
+
+            if view.hand.len() == 1 {
+                score += 1000;
+                if view.play_out {
+                    reasons.push("Plays your last card and takes the next place".to_string());
+                } else {
+                    reasons.push("Plays your last card and wins the match".to_string());
+                }
+            }
+
+            if view.pending_penalty > 0 {
+                if effect == SpecialEffect::ChooseShape {
+                    score += 20;
+                    reasons.push("Avoids the penalty without a matching defence".to_string());
+                } else {
+                    score += 100;
+                    reasons.push(format!("Defends the Pick {} penalty", view.pending_penalty));
+                }
+            }
+
+            match effect {
+                SpecialEffect::ChooseShape => {
+                    if legal.len() > 1 && view.pending_penalty == 0 {
+                        score -= 40;
+                        reasons.push("Hold your Whot for when you have no other play".to_string());
+                    } else {
+                        score += 10;
+                        reasons.push("Whot lets you choose the next suit".to_string());
+                    }
+                }
+                SpecialEffect::DrawTwo | SpecialEffect::DrawThree if view.pending_penalty == 0 => {
+                    if opponent_close {
+                        score += 30;
+                        reasons.push("Makes an opponent close to winning draw cards".to_string());
+                    } else if defences_held == 1 {
+                        score -= 15;
+                        reasons.push("Your only defence against Pick Two and Pick Three".to_string());
+                    }
+                }
+                SpecialEffect::SkipNext | SpecialEffect::AllDrawOne if opponent_close => {
+                    score += 20;
+                    reasons.push("Slows down an opponent close to winning".to_string());
+                }
+                SpecialEffect::PlayAgain if view.hand.len() > 2 => {
+                    score += 5;
+                    reasons.push("Hold On lets you play again".to_string());
+                }
+                _ => {}
+            }
+
+            if effect != SpecialEffect::ChooseShape {
+                score += points as i32;
+                reasons.push(format!("Dumps {} points from your hand", points));
+            }
+
+            if view.active_demand == Some(card.suit) && effect != SpecialEffect::ChooseShape {
+                reasons.push("Answers the demanded suit".to_string());
+            }
+
+            MoveEvaluation {
+                card,
+                chosen_suit: (card.value == CardValue::Whot)
+                    .then(|| Tactician.choose_suit(view, card)),
+                score,
+                reasons,
+            }
+        })
+        .collect();
+
+    // Stable sort keeps hand order between equally scored moves
+    evaluations.sort_by(|a, b| b.score.cmp(&a.score));
+    evaluations
+}
+
+// ============ Helpers ============
+
+const SUITS: [CardSuit; 5] = [
+    CardSuit::Circle,
+    CardSuit::Cross,
+    CardSuit::Triangle,
+    CardSuit::Square,
+    CardSuit::Star,
+];
+
+/// Play the highest-value card, keeping Whot as a last resort
+fn shed_highest<'a>(legal: &[&'a Card]) -> Option<&'a Card> {
+    legal
+        .iter()
+        .filter(|card| card.value != CardValue::Whot)
+        .max_by_key(|card| GameEngine::card_points(card))
+        .or_else(|| legal.first())
+        .copied()
+}
+
+/// Whether a card can answer a Pick Two or Pick Three
+fn is_defence(card: &Card) -> bool {
+    matches!(
+        GameEngine::get_card_effect(card),
+        SpecialEffect::DrawTwo | SpecialEffect::DrawThree
+    )
+}
+
+/// Most common suit among the hand's other non-Whot cards
+fn most_common_suit(hand: &[Card], played_id: u32) -> CardSuit {
+    SUITS
+        .into_iter()
+        .max_by_key(|&suit| {
+            hand.iter()
+                .filter(|card| card.id != played_id && card.value != CardValue::Whot)
+                .filter(|card| card.suit == suit)
+                .count()
+        })
+        .unwrap_or(CardSuit::Circle)
+}
@@ -1,15 +1,41 @@
 #![cfg_attr(target_arch = "wasm32", no_main)]
 
+mod bot;
 mod game_engine;
 mod state;
 
 use linera_sdk::{
-    abi::WithContractAbi, linera_base_types::AccountOwner, views::View, Contract, ContractRuntime,
+    abi::WithContractAbi,
+    linera_base_types::{AccountOwner, StreamName},
+    views::View,
+    Contract, ContractRuntime,
 };
 
-use crate::game_engine::{GameEngine, GameResult, SpecialEffect};
-use crate::state::{LinotState, MatchConfig, MatchData, MatchStatus, Player};
-use linot::{CardSuit, LinotAbi, LinotError, Message, Operation};
+use crate::bot::BotMove;
+use crate::game_engine::{GameEngine, GameResult};
+use crate::state::{
+    AutoActions, LinotState, MatchConfig, MatchData, MatchEvent, MatchEventKind, MatchStatus,
+    ChatAllowance, ChatMessage, Player, Profile, SessionKey, TakebackPoint, TurnPhase,
+};
+use linot::{
+    BotDifficulty, CardSuit, Emote, LinotAbi, LinotError, LinotResponse, MatchResult, Message, Operation,
+    PublicMatchState, PublicSeat, SeatReservation, SessionGrant, RESULTS_STREAM,
+};
+
+/// Upper bound on bot and standing-order moves executed within a single operation
+const MAX_AUTOMATIC_MOVES: usize = 64;
+
+/// Longest chat message, in characters
+const MAX_CHAT_LENGTH: usize = 200;
+
+/// Chat messages an author may post per rate-limit window
+const CHAT_BURST: u32 = 5;
+
+/// Length of the chat rate-limit window (10 seconds)
+const CHAT_WINDOW_MICROS: u64 = 10_000_000;
+
+/// Longest a session key may stay valid (one day)
+const MAX_SESSION_SECS: u64 = 86_400;
 
 pub struct LinotContract {
     state: LinotState,
@@ -26,7 +52,7 @@ impl Contract for LinotContract {
     type Message = Message;
     type Parameters = ();
     type InstantiationArgument = MatchConfig;
-    type EventValue = ();
+    type EventValue = MatchResult;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         let state = LinotState::load(runtime.root_view_storage_context())
@@ -40,14 +66,19 @@ impl Contract for LinotContract {
         if config.host.is_none() {
             config.host = self.runtime.authenticated_signer();
         }
+        if let Err(e) = Self::validate_config(&config) {
+            panic!("Invalid match configuration: {}", e);
+        }
         
         // Store match configuration
         self.state.config.set(config);
 
         // Initialize empty match data
-        let mut match_data = MatchData::default();
-        match_data.created_at = self.runtime.system_time().micros();
-        match_data.status = MatchStatus::Waiting;
+        let match_data = MatchData {
+            created_at: self.runtime.system_time().micros(),
+            status: MatchStatus::Waiting,
+            ..MatchData::default()
+        };
 
         self.state.match_data.set(match_data);
 
@@ -56,50 +87,109 @@ impl Contract for LinotContract {
     }
 
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
+        // Read-only application calls change nothing and need no caller
+        match operation {
+            Operation::GetPublicState => return LinotResponse::PublicState(Box::new(self.public_state())),
+            Operation::GetResult => {
+                return LinotResponse::Result(GameEngine::match_result(self.state.match_data.get()))
+            }
+            _ => {}
+        }
+
+        // Calls from another application without a signer act as that application
         let caller = self
             .runtime
             .authenticated_signer()
+            .or_else(|| self.runtime.authenticated_caller_id().map(AccountOwner::from))
             .ok_or(LinotError::CallerRequired)
             .expect("Caller required");
 
-        let result = match operation {
-            Operation::JoinMatch { nickname } => {
-                self.handle_join_match(caller, nickname).await
-            }
-            Operation::StartMatch => {
-                self.handle_start_match(caller).await
-            }
-            Operation::PlayCard {
-                card_index,
-                chosen_suit,
-            } => {
-                self.handle_play_card(caller, card_index, chosen_suit).await
-            }
-            Operation::DrawCard => {
-                self.handle_draw_card(caller).await
-            }
-            Operation::CallLastCard => {
-                self.handle_call_last_card(caller).await
-            }
-            Operation::ChallengeLastCard { player_index } => {
-                self.handle_challenge_last_card(caller, player_index).await
-            }
-            Operation::LeaveMatch => {
-                self.handle_leave_match(caller).await
+        // A seat's session key acts for the seat's owner, within its scope
+        let caller = match self.session_owner(caller, &operation) {
+            Ok(owner) => owner,
+            Err(e) => panic!("Operation failed: {}", e),
+        };
+
+        // Chat and profiles do not touch the match, so they leave the version alone
+        let operation = match operation {
+            Operation::SendChat { text, emote } => {
+                if let Err(e) = self.handle_send_chat(caller, text, emote).await {
+                    panic!("Operation failed: {}", e);
+                }
+                return LinotResponse::Ok;
             }
-            Operation::PlaceBet {
-                player_index: _,
-                amount: _,
-            } => {
-                Err(LinotError::BettingNotImplemented)
+            Operation::RegisterProfile { nickname } => {
+                if let Err(e) = self.handle_register_profile(caller, nickname).await {
+                    panic!("Operation failed: {}", e);
+                }
+                return LinotResponse::Ok;
             }
+            operation => operation,
         };
 
+        let was_finished = self.state.match_data.get().status == MatchStatus::Finished;
+        let turn_before = Self::turn_holder(self.state.match_data.get());
+
+        // Moves in unranked matches can be taken back, so keep the state before them
+        let is_move = matches!(
+            operation,
+            Operation::PlayCard { .. } | Operation::ChooseSuit { .. } | Operation::DrawCard { .. }
+        );
+        let is_takeback = matches!(
+            operation,
+            Operation::RequestTakeback { .. }
+                | Operation::AcceptTakeback { .. }
+                | Operation::DeclineTakeback { .. }
+        );
+        let before = (is_move && !self.state.config.get().is_ranked)
+            .then(|| self.state.match_data.get().clone());
+
+        let result = self.dispatch_operation(caller, operation).await;
+
         // Panic on error to maintain existing behavior
         // TODO: Return Result from execute_operation in future
         if let Err(e) = result {
             panic!("Operation failed: {}", e);
         }
+
+        // Only the latest move can be taken back, and only until something else happens
+        if let Some(before) = before {
+            self.record_takeback_point(caller, before);
+        } else if !is_takeback {
+            self.clear_takeback();
+        }
+
+        // A pause request only stands until play moves on
+        let turn_after = Self::turn_holder(self.state.match_data.get());
+        let match_data = self.state.match_data.get();
+        if (is_move || turn_after != turn_before)
+            && match_data.status == MatchStatus::InProgress
+            && !match_data.pause_votes.is_empty()
+        {
+            self.state.match_data.get_mut().pause_votes.clear();
+        }
+
+        // Restart the idle clock whenever the turn changes hands
+        if turn_after != turn_before {
+            let now = self.runtime.system_time().micros();
+            let match_data = self.state.match_data.get_mut();
+            match_data.turn_started_at = now;
+            if let Some(seat) = turn_after {
+                let event = MatchEvent::new(match_data.version + 1, Some(seat), MatchEventKind::TurnChanged);
+                self.state.events.push(event);
+            }
+        }
+
+        self.bump_version();
+
+        // Announce the result to subscribed applications
+        if !was_finished {
+            if let Some(result) = GameEngine::match_result(self.state.match_data.get()) {
+                self.runtime.emit(StreamName(RESULTS_STREAM.to_vec()), &result);
+            }
+        }
+
+        LinotResponse::Ok
     }
 
     async fn execute_message(&mut self, message: Self::Message) {
@@ -113,7 +203,11 @@ impl Contract for LinotContract {
                 Ok(())
             }
             Message::PlayerJoined { player, nickname } => {
-                self.handle_remote_join(player, nickname).await
+                let result = self.handle_remote_join(player, nickname).await;
+                if result.is_ok() {
+                    self.bump_version();
+                }
+                result
             }
             Message::StateUpdate {
                 current_player: _,
@@ -138,324 +232,1786 @@ impl Contract for LinotContract {
 }
 
 impl LinotContract {
-    /// Handle player joining the match
-    async fn handle_join_match(&mut self, caller: AccountOwner, nickname: String) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
-        let mut config = self.state.config.get().clone();
-
-        // Validate: match must be waiting
-        if match_data.status != MatchStatus::Waiting {
-            return Err(LinotError::MatchAlreadyStarted);
+    /// Check the client's expected version and route the operation to its handler
+    async fn dispatch_operation(
+        &mut self,
+        caller: AccountOwner,
+        operation: Operation,
+    ) -> Result<(), LinotError> {
+        // Reject operations built on a stale view of the match
+        if let Some(expected) = operation.expected_version() {
+            let current = self.state.match_data.get().version;
+            if expected != current {
+                return Err(LinotError::StaleVersion { expected, current });
+            }
         }
 
-        // Validate: not at max players
-        if match_data.players.len() >= config.max_players as usize {
-            return Err(LinotError::MatchFull(config.max_players));
+        match operation {
+            Operation::JoinMatch { nickname, invite_code, session, .. } => {
+                self.handle_join_match(caller, nickname, invite_code, session).await
+            }
+            Operation::AddBot { difficulty, .. } => {
+                self.handle_add_bot(caller, difficulty).await
+            }
+            Operation::SetReady { ready, .. } => {
+                self.handle_set_ready(caller, ready).await
+            }
+            Operation::ExpireLobby { .. } => {
+                self.handle_expire_lobby().await
+            }
+            Operation::StartMatch { .. } => {
+                self.handle_start_match(caller).await
+            }
+            Operation::PlayCard {
+                card_id,
+                chosen_suit,
+                ..
+            } => {
+                self.handle_play_card(caller, card_id, chosen_suit).await
+            }
+            Operation::ChooseSuit { suit, .. } => {
+                self.handle_choose_suit(caller, suit).await
+            }
+            Operation::DrawCard { .. } => {
+                self.handle_draw_card(caller).await
+            }
+            Operation::CallLastCard { .. } => {
+                self.handle_call_last_card(caller).await
+            }
+            Operation::SetAutoActions {
+                auto_draw,
+                auto_defend,
+                auto_call_last_card,
+                ..
+            } => {
+                let orders = AutoActions {
+                    auto_draw,
+                    auto_defend,
+                    auto_call_last_card,
+                };
+                self.handle_set_auto_actions(caller, orders).await
+            }
+            Operation::ChallengeLastCard { player_index, .. } => {
+                self.handle_challenge_last_card(caller, player_index).await
+            }
+            Operation::LeaveMatch { .. } => {
+                self.handle_leave_match(caller).await
+            }
+            Operation::StepAway { .. } => {
+                self.handle_step_away(caller).await
+            }
+            Operation::MarkAway { player_index, .. } => {
+                self.handle_mark_away(caller, player_index).await
+            }
+            Operation::Rejoin { .. } => {
+                self.handle_rejoin(caller).await
+            }
+            Operation::ForfeitAway { player_index, .. } => {
+                self.handle_forfeit_away(caller, player_index).await
+            }
+            Operation::RequestPause { .. } => {
+                self.handle_pause_vote(caller, true).await
+            }
+            Operation::AcceptPause { .. } => {
+                self.handle_pause_vote(caller, false).await
+            }
+            Operation::Resume { .. } => {
+                self.handle_resume(caller).await
+            }
+            Operation::RequestTakeback { .. } => {
+                self.handle_request_takeback(caller).await
+            }
+            Operation::AcceptTakeback { .. } => {
+                self.handle_accept_takeback(caller).await
+            }
+            Operation::DeclineTakeback { .. } => {
+                self.handle_decline_takeback(caller).await
+            }
+            Operation::AuthorizeSession { session, .. } => {
+                self.handle_authorize_session(caller, session).await
+            }
+            Operation::RevokeSession { .. } => {
+                self.handle_revoke_session(caller).await
+            }
+            Operation::PlaceBet { .. } => {
+                Err(LinotError::BettingNotImplemented)
+            }
+            Operation::CreateMatch { config, .. } => {
+                self.handle_create_match(caller, config).await
+            }
+            Operation::RegisterProfile { .. }
+            | Operation::SendChat { .. }
+            | Operation::GetPublicState
+            | Operation::GetResult => Ok(()),
         }
+    }
 
-        // Validate: player not already joined
-        if match_data.players.iter().any(|p| p.owner == caller) {
-            return Err(LinotError::PlayerAlreadyJoined);
+    /// Seat owner an operation is submitted for
+    ///
+    /// A signer that holds no seat but is a seat's session key acts for that
+    /// seat's owner, as long as the key has not expired and its scope covers
+    /// the operation. Anyone else acts for themselves.
+    fn session_owner(&mut self, signer: AccountOwner, operation: &Operation) -> Result<AccountOwner, LinotError> {
+        let now = self.runtime.system_time().micros();
+        let players = &self.state.match_data.get().players;
+        if players.iter().any(|p| p.owner == signer) {
+            return Ok(signer);
         }
 
-        // Make first player the host (for single-player demo)
-        if match_data.players.is_empty() {
-            config.host = Some(caller);
-            self.state.config.set(config);
+        let Some((owner, session)) = players
+            .iter()
+            .find_map(|p| p.session.as_ref().filter(|s| s.key == signer).map(|s| (p.owner, s)))
+        else {
+            return Ok(signer);
+        };
+
+        if now >= session.expires_at {
+            return Err(LinotError::SessionKeyExpired);
+        }
+        if !session.scope.allows(operation) {
+            return Err(LinotError::SessionNotPermitted);
         }
 
-        // Add player
-        match_data.players.push(Player::new(caller, nickname));
-        self.state.match_data.set(match_data);
-        
-        Ok(())
+        Ok(owner)
     }
 
-    /// Handle starting the match
-    async fn handle_start_match(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
-
-        // Validate: caller must be a player
-        if !match_data.players.iter().any(|p| p.owner == caller) {
-            return Err(LinotError::OnlyHostCanStart);
+    /// Turn a grant from a seat's owner into a session key for that seat
+    fn issue_session(&mut self, owner: AccountOwner, grant: SessionGrant, match_data: &MatchData) -> Result<SessionKey, LinotError> {
+        // Validate: a bounded lifetime
+        if !(1..=MAX_SESSION_SECS).contains(&grant.lifetime_secs) {
+            return Err(LinotError::InvalidSessionLifetime(MAX_SESSION_SECS));
         }
 
-        // For single-player demo: Allow starting with just 1 player (you vs local AI)
-        // In multiplayer, this would require 2+ players
-        if match_data.players.is_empty() {
-            return Err(LinotError::NotEnoughPlayers(1));
+        // Validate: the key is nobody's seat and no other seat's key
+        let taken = match_data.players.iter().any(|p| {
+            p.owner == grant.key
+                || (p.owner != owner && p.session.as_ref().is_some_and(|s| s.key == grant.key))
+        });
+        if grant.key == owner || taken {
+            return Err(LinotError::InvalidSessionKey);
         }
 
-        // Validate: match is waiting
-        if match_data.status != MatchStatus::Waiting {
-            return Err(LinotError::MatchAlreadyStarted);
-        }
+        let now = self.runtime.system_time().micros();
+        Ok(SessionKey {
+            key: grant.key,
+            expires_at: now.saturating_add(grant.lifetime_secs.saturating_mul(1_000_000)),
+            scope: grant.scope,
+        })
+    }
 
-        // Create and shuffle deck
-        let mut deck = GameEngine::create_deck();
-        let chain_id = self.runtime.chain_id();
-        let seed = chain_id.to_string();
-        GameEngine::shuffle_with_seed(&mut deck, seed.as_bytes());
+    /// Handle a seated player authorizing (or replacing) their seat's session key
+    async fn handle_authorize_session(&mut self, caller: AccountOwner, session: SessionGrant) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
 
-        // Deal initial hands (6 cards each)
-        let hands = GameEngine::deal_initial_hands(&mut deck, match_data.players.len());
-        for (i, player) in match_data.players.iter_mut().enumerate() {
-            player.cards = hands[i].clone();
-            player.update_card_count();
-        }
+        let seat = Self::active_seat(&match_data, caller)?;
+        let session = self.issue_session(caller, session, &match_data)?;
+        match_data.players[seat].session = Some(session);
+        self.state.match_data.set(match_data);
 
-        // Place first card in discard pile
-        if let Some(first_card) = deck.pop() {
-            match_data.discard_pile.push(first_card);
-        }
+        Ok(())
+    }
 
-        // Update match state
-        match_data.deck = deck;
-        match_data.status = MatchStatus::InProgress;
-        match_data.current_player_index = 0;
+    /// Handle a seated player revoking their seat's session key
+    async fn handle_revoke_session(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
 
+        let seat = Self::active_seat(&match_data, caller)?;
+        if match_data.players[seat].session.take().is_none() {
+            return Err(LinotError::NoSessionKey);
+        }
         self.state.match_data.set(match_data);
-        
+
         Ok(())
     }
 
-    /// Handle playing a card
-    async fn handle_play_card(
-        &mut self,
-        caller: AccountOwner,
-        card_index: usize,
-        chosen_suit: Option<CardSuit>,
-    ) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
+    /// Handle a player registering (or renaming) their persistent name
+    async fn handle_register_profile(&mut self, caller: AccountOwner, nickname: String) -> Result<(), LinotError> {
+        let nickname = linot::validate_nickname(&nickname)?;
+        let key = linot::nickname_key(&nickname);
 
-        // Validate: match is in progress
-        if match_data.status != MatchStatus::InProgress {
-            return Err(LinotError::MatchNotInProgress);
+        // Validate: nobody else has registered the name
+        let holder = self
+            .state
+            .profile_names
+            .get(&key)
+            .await
+            .expect("Failed to read profile names");
+        if holder.is_some_and(|owner| owner != caller) {
+            return Err(LinotError::NicknameTaken);
         }
 
-        // Validate: it's caller's turn
-        let current_player = &mut match_data.players[match_data.current_player_index];
-        if current_player.owner != caller {
-            return Err(LinotError::NotYourTurn);
+        // Validate: nobody else is seated under the name right now
+        let seated = &self.state.match_data.get().players;
+        if seated.iter().any(|p| p.owner != caller && linot::nickname_key(&p.nickname) == key) {
+            return Err(LinotError::NicknameTaken);
         }
 
-        // Validate: card index is valid
-        if card_index >= current_player.cards.len() {
-            return Err(LinotError::InvalidCardIndex(card_index));
+        // Release the previous name
+        let previous = self
+            .state
+            .profiles
+            .get(&caller)
+            .await
+            .expect("Failed to read profile");
+        if let Some(previous) = previous {
+            self.state
+                .profile_names
+                .remove(&linot::nickname_key(&previous.nickname))
+                .expect("Failed to release profile name");
         }
 
-        // Get the card
-        let card = current_player.cards[card_index].clone();
+        self.state
+            .profile_names
+            .insert(&key, caller)
+            .expect("Failed to store profile name");
+        self.state
+            .profiles
+            .insert(
+                &caller,
+                Profile {
+                    owner: caller,
+                    nickname,
+                    registered_at: self.runtime.system_time().micros(),
+                },
+            )
+            .expect("Failed to store profile");
+
+        Ok(())
+    }
 
-        // Get top card from discard pile
-        let top_card = match_data
-            .discard_pile
-            .last()
-            .ok_or(LinotError::NoCardInDiscardPile)?;
+    /// Name a joining player is seated under
+    ///
+    /// A registered profile name always wins; otherwise the given nickname
+    /// must follow the naming rules and not be someone else's registered name.
+    /// Either way it must be unique at the table.
+    async fn resolve_nickname(&mut self, caller: AccountOwner, nickname: Option<String>, match_data: &MatchData) -> Result<String, LinotError> {
+        let profile = self
+            .state
+            .profiles
+            .get(&caller)
+            .await
+            .expect("Failed to read profile");
+
+        let nickname = match (profile, nickname) {
+            (Some(profile), _) => profile.nickname,
+            (None, Some(nickname)) => {
+                let nickname = linot::validate_nickname(&nickname)?;
+                let holder = self
+                    .state
+                    .profile_names
+                    .get(&linot::nickname_key(&nickname))
+                    .await
+                    .expect("Failed to read profile names");
+                if holder.is_some_and(|owner| owner != caller) {
+                    return Err(LinotError::NicknameTaken);
+                }
+                nickname
+            }
+            (None, None) => return Err(LinotError::NicknameRequired),
+        };
 
-        // Validate: card can be played
-        if !GameEngine::is_valid_play(
-            &card,
-            top_card,
-            match_data.active_shape_demand,
-            match_data.pending_penalty,
-        ) {
-            return Err(LinotError::InvalidCardPlay);
+        // Validate: unique at this table (bots included)
+        let key = linot::nickname_key(&nickname);
+        if match_data.players.iter().any(|p| linot::nickname_key(&p.nickname) == key) {
+            return Err(LinotError::NicknameTaken);
         }
 
-        // Remove card from hand
-        current_player.cards.remove(card_index);
-        current_player.update_card_count();
+        Ok(nickname)
+    }
 
-        // Add to discard pile
-        match_data.discard_pile.push(card.clone());
+    /// Handle a player (or a spectator, if allowed) posting to the match chat
+    async fn handle_send_chat(&mut self, caller: AccountOwner, text: Option<String>, emote: Option<Emote>) -> Result<(), LinotError> {
+        let match_data = self.state.match_data.get();
+        let seat = match_data.players.iter().position(|p| p.owner == caller);
 
-        // Check if player should call last card
-        if current_player.card_count == 1 && !current_player.called_last_card {
-            // Automatic last card call in V1
-            current_player.called_last_card = true;
+        // Validate: seated players, plus spectators where the table allows them
+        if seat.is_none() && !self.state.config.get().spectator_chat {
+            return Err(LinotError::ChatNotAllowed);
         }
 
-        // Apply special card effect
-        let effect = GameEngine::get_card_effect(&card);
-        GameEngine::apply_effect(&mut match_data, effect, chosen_suit);
-
-        // Check if game ended
-        if let Some(result) = GameEngine::check_game_end(&match_data) {
-            match result {
-                GameResult::Winner(idx) => {
-                    match_data.winner_index = Some(idx);
-                    match_data.status = MatchStatus::Finished;
+        // Validate: either a short text or an emote
+        let text = text.map(|text| text.trim().to_string());
+        match (&text, emote) {
+            (Some(text), None) if !text.is_empty() => {
+                if text.chars().count() > MAX_CHAT_LENGTH {
+                    return Err(LinotError::ChatTooLong(MAX_CHAT_LENGTH));
                 }
-                GameResult::Draw => {
-                    match_data.status = MatchStatus::Finished;
+                if text.chars().any(char::is_control) {
+                    return Err(LinotError::InvalidChatMessage);
                 }
             }
+            (None, Some(_)) => {}
+            _ => return Err(LinotError::InvalidChatMessage),
         }
 
-        // Handle General Market effect if needed
-        if let SpecialEffect::AllDrawOne = effect {
-            Self::apply_general_market(&mut match_data);
+        // Validate: at most CHAT_BURST messages per window
+        let now = self.runtime.system_time().micros();
+        let mut allowance = self
+            .state
+            .chat_allowance
+            .get(&caller)
+            .await
+            .expect("Failed to read chat allowance")
+            .unwrap_or_default();
+        if now.saturating_sub(allowance.window_start) >= CHAT_WINDOW_MICROS {
+            allowance = ChatAllowance {
+                window_start: now,
+                count: 0,
+            };
         }
-
-        // Advance turn based on effect
-        if effect == SpecialEffect::PlayAgain {
-            // Hold On (1): Current player plays again, don't advance
-        } else if effect == SpecialEffect::SkipNext {
-            // Suspension (8): Skip next player by advancing twice
-            GameEngine::advance_turn(&mut match_data);
-            GameEngine::advance_turn(&mut match_data);
-        } else {
-            // Normal: Advance to next player
-            GameEngine::advance_turn(&mut match_data);
+        if allowance.count >= CHAT_BURST {
+            return Err(LinotError::ChatRateLimited);
         }
+        allowance.count += 1;
+        self.state
+            .chat_allowance
+            .insert(&caller, allowance)
+            .expect("Failed to store chat allowance");
+
+        self.state.chat.push(ChatMessage {
+            author: caller,
+            seat,
+            text,
+            emote,
+            sent_at: now,
+        });
 
-        self.state.match_data.set(match_data);
-        
         Ok(())
     }
 
-    /// Handle drawing a card
-    async fn handle_draw_card(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
+    /// Seat holding the turn, if a match is in progress (or paused)
+    fn turn_holder(match_data: &MatchData) -> Option<usize> {
+        matches!(match_data.status, MatchStatus::InProgress | MatchStatus::Paused)
+            .then_some(match_data.current_player_index)
+    }
 
-        // Validate: it's caller's turn
-        let current_player_idx = match_data.current_player_index;
-        let current_player = &mut match_data.players[current_player_idx];
-        if current_player.owner != caller {
-            return Err(LinotError::NotYourTurn);
-        }
+    /// Advance the state version after a successful state transition
+    fn bump_version(&mut self) {
+        self.state.match_data.get_mut().version += 1;
 
-        // Determine how many cards to draw
-        let cards_to_draw = if match_data.pending_penalty > 0 {
-            let count = match_data.pending_penalty;
-            match_data.pending_penalty = 0;
-            count
-        } else {
-            1
-        };
+        // Every transition goes through here, so audit the result in debug builds
+        #[cfg(debug_assertions)]
+        {
+            let violations = GameEngine::audit(self.state.match_data.get());
+            assert!(violations.is_empty(), "Match state audit failed: {:?}", violations);
+        }
+    }
 
-        // Draw cards
-        for _ in 0..cards_to_draw {
-            if match_data.deck.is_empty() {
-                // Reshuffle discard pile (except top card)
-                if match_data.discard_pile.len() > 1 {
-                    let top_card = match_data.discard_pile.pop().unwrap();
-                    match_data.deck = match_data.discard_pile.clone();
-                    match_data.discard_pile.clear();
-                    match_data.discard_pile.push(top_card);
-
-                    // Reshuffle with new seed
-                    match_data.round_number += 1;
-                    let seed = format!("{}{}", self.runtime.chain_id(), match_data.round_number);
-                    GameEngine::shuffle_with_seed(&mut match_data.deck, seed.as_bytes());
-                } else {
-                    break; // No more cards available
-                }
-            }
+    /// Handle the host (or a hosting application) setting up a new match on this table
+    async fn handle_create_match(&mut self, caller: AccountOwner, mut config: MatchConfig) -> Result<(), LinotError> {
+        let match_data = self.state.match_data.get().clone();
 
-            if let Some(card) = match_data.deck.pop() {
-                current_player.cards.push(card);
-            }
+        // Validate: only the host may reconfigure the table
+        if self.state.config.get().host.is_some_and(|host| host != caller) {
+            return Err(LinotError::OnlyHostCanCreateMatch);
         }
 
-        current_player.update_card_count();
+        // Validate: nobody is seated at an unfinished match
+        let empty = match_data.status == MatchStatus::Waiting && match_data.players.is_empty();
+        let over = matches!(match_data.status, MatchStatus::Finished | MatchStatus::Cancelled);
+        if !empty && !over {
+            return Err(LinotError::TableInUse);
+        }
 
-        // Clear active shape demand after drawing
-        match_data.active_shape_demand = None;
+        Self::validate_config(&config)?;
+        if config.host.is_none() {
+            config.host = Some(caller);
+        }
+        self.state.config.set(config);
 
-        // Advance turn
-        GameEngine::advance_turn(&mut match_data);
+        // Fresh match; the version keeps counting so clients see the change
+        self.state.match_data.set(MatchData {
+            created_at: self.runtime.system_time().micros(),
+            status: MatchStatus::Waiting,
+            match_number: match_data.match_number + 1,
+            version: match_data.version,
+            ..MatchData::default()
+        });
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            None,
+            MatchEventKind::MatchCreated,
+        ));
 
-        self.state.match_data.set(match_data);
-        
         Ok(())
     }
 
-    /// Handle calling last card
-    async fn handle_call_last_card(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
-
-        if let Some(player) = match_data.players.iter_mut().find(|p| p.owner == caller) {
-            player.called_last_card = true;
+    /// Check that seat reservations fit the table and name each seat and player once
+    fn validate_config(config: &MatchConfig) -> Result<(), LinotError> {
+        // Validate: partnerships are two teams of two, played to the first team out
+        if config.team_mode && config.max_players != 4 {
+            return Err(LinotError::TeamModeNeedsPlayers(4));
+        }
+        if config.team_mode && config.play_out {
+            return Err(LinotError::PlayOutWithTeams);
         }
 
-        self.state.match_data.set(match_data);
-        
+        for (i, reservation) in config.reserved_seats.iter().enumerate() {
+            let earlier = &config.reserved_seats[..i];
+            if reservation.seat >= config.max_players
+                || earlier.iter().any(|r| r.seat == reservation.seat || r.owner == reservation.owner)
+            {
+                return Err(LinotError::InvalidReservation(reservation.seat));
+            }
+        }
         Ok(())
     }
 
-    /// Handle challenging a player who didn't call last card
-    async fn handle_challenge_last_card(&mut self, _caller: AccountOwner, player_index: usize) -> Result<(), LinotError> {
-        let mut match_data = self.state.match_data.get().clone();
+    /// Number of seats still held for reserved players who have not joined
+    fn held_seats(config: &MatchConfig, match_data: &MatchData) -> usize {
+        config
+            .reserved_seats
+            .iter()
+            .filter(|reservation| !match_data.players.iter().any(|p| p.owner == reservation.owner))
+            .count()
+    }
 
-        // Validate player index
-        if player_index >= match_data.players.len() {
-            return Err(LinotError::InvalidPlayerIndex(player_index));
+    /// Move reserved players to their seats, filling the rest in join order
+    ///
+    /// A reservation beyond the number of seated players cannot be honoured
+    /// and that player simply keeps their place in the join order.
+    fn seat_reserved_players(reservations: &[SeatReservation], players: Vec<Player>) -> Vec<Player> {
+        let mut seats: Vec<Option<Player>> = (0..players.len()).map(|_| None).collect();
+        let mut others = Vec::new();
+
+        for player in players {
+            let seat = reservations
+                .iter()
+                .find(|reservation| reservation.owner == player.owner)
+                .map(|reservation| reservation.seat as usize)
+                .filter(|&seat| seat < seats.len());
+            match seat {
+                Some(seat) => seats[seat] = Some(player),
+                None => others.push(player),
+            }
         }
 
-        let player = &mut match_data.players[player_index];
+        let mut others = others.into_iter();
+        seats
+            .into_iter()
+            .map(|seat| seat.or_else(|| others.next()).expect("every seat is filled"))
+            .collect()
+    }
 
-        // If player has 1 card and didn't call last card, penalty
-        if player.card_count == 1 && !player.called_last_card {
-            // Draw 2 cards as penalty
-            for _ in 0..2 {
-                if let Some(card) = match_data.deck.pop() {
-                    player.cards.push(card);
-                }
-            }
-            player.update_card_count();
+    /// Public match state, as returned to calling applications
+    fn public_state(&self) -> PublicMatchState {
+        let match_data = self.state.match_data.get();
+
+        PublicMatchState {
+            config: self.state.config.get().clone(),
+            status: match_data.status,
+            players: match_data
+                .players
+                .iter()
+                .map(|p| PublicSeat {
+                    owner: p.owner,
+                    nickname: p.nickname.clone(),
+                    card_count: p.card_count,
+                    is_active: p.is_active,
+                    bot: p.bot,
+                    ready: p.ready,
+                    away: p.away_since.is_some(),
+                })
+                .collect(),
+            current_player_index: match_data.current_player_index,
+            top_card: match_data.discard_pile.last().cloned(),
+            active_shape_demand: match_data.active_shape_demand,
+            pending_penalty: match_data.pending_penalty,
+            deck_size: match_data.deck.len(),
+            lobby_expires_at: self.lobby_deadline(match_data),
+            placements: Self::owners(match_data, &match_data.placements),
+            left_order: Self::owners(match_data, &match_data.left_order),
+            version: match_data.version,
         }
+    }
 
-        self.state.match_data.set(match_data);
-        
-        Ok(())
+    /// Owners of the given seats, in the same order
+    fn owners(match_data: &MatchData, seats: &[usize]) -> Vec<AccountOwner> {
+        seats.iter().map(|&seat| match_data.players[seat].owner).collect()
     }
 
-    /// Handle player leaving/forfeiting
-    async fn handle_leave_match(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+    /// Handle player joining the match
+    async fn handle_join_match(&mut self, caller: AccountOwner, nickname: Option<String>, invite_code: Option<String>, session: Option<SessionGrant>) -> Result<(), LinotError> {
         let mut match_data = self.state.match_data.get().clone();
+        let mut config = self.state.config.get().clone();
 
-        // Mark player as inactive
-        if let Some(player) = match_data.players.iter_mut().find(|p| p.owner == caller) {
-            player.is_active = false;
+        // Validate: match must be waiting
+        if match_data.status != MatchStatus::Waiting {
+            return Err(LinotError::MatchAlreadyStarted);
         }
+        self.check_lobby_open(&match_data)?;
 
-        // Check if only one active player left
-        let active_players: Vec<_> = match_data.players.iter().filter(|p| p.is_active).collect();
-        if active_players.len() == 1 {
-            // Remaining player wins
-            let winner_idx = match_data.players.iter().position(|p| p.is_active).unwrap();
-            match_data.winner_index = Some(winner_idx);
-            match_data.status = MatchStatus::Finished;
+        // Validate: not at max players
+        if match_data.players.len() >= config.max_players as usize {
+            return Err(LinotError::MatchFull(config.max_players));
+        }
+
+        // Validate: player not already joined
+        if match_data.players.iter().any(|p| p.owner == caller) {
+            return Err(LinotError::PlayerAlreadyJoined);
+        }
+
+        // Validate: private tables only seat invited players
+        let reserved = config.reserved_seat(caller).is_some();
+        let invited = reserved || config.host == Some(caller) || config.allowlist.contains(&caller);
+        if config.is_private() && !invited {
+            match (&config.invite_code_hash, &invite_code) {
+                (Some(hash), Some(code)) if linot::hash_invite_code(code) == *hash => {}
+                (Some(_), Some(_)) => return Err(LinotError::InvalidInviteCode),
+                _ => return Err(LinotError::NotInvited),
+            }
+        }
+
+        // Validate: the open seats are not all held for reserved players
+        if !reserved && match_data.players.len() + Self::held_seats(&config, &match_data) >= config.max_players as usize {
+            return Err(LinotError::SeatReserved);
+        }
+
+        let nickname = self.resolve_nickname(caller, nickname, &match_data).await?;
+        let session = session
+            .map(|grant| self.issue_session(caller, grant, &match_data))
+            .transpose()?;
+
+        // Make first player the host unless the table already has one
+        // (a hosting application keeps the right to reset its table)
+        if config.host.is_none() {
+            config.host = Some(caller);
+            self.state.config.set(config);
         }
 
+        // Add player
+        match_data.players.push(Player {
+            session,
+            ..Player::new(caller, nickname)
+        });
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(match_data.players.len() - 1),
+            MatchEventKind::PlayerJoined,
+        ));
         self.state.match_data.set(match_data);
         
         Ok(())
     }
 
-    /// Handle remote player join (cross-chain)
-    async fn handle_remote_join(&mut self, player: AccountOwner, nickname: String) -> Result<(), LinotError> {
-        // In V1, we treat this the same as local join
-        self.handle_join_match(player, nickname).await
-    }
+    /// Handle the host filling an open seat with a bot
+    async fn handle_add_bot(&mut self, caller: AccountOwner, difficulty: BotDifficulty) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+        let config = self.state.config.get().clone();
 
-    /// Apply General Market effect (all other players draw 1)
-    fn apply_general_market(match_data: &mut MatchData) {
-        let current_idx = match_data.current_player_index;
-        for (i, player) in match_data.players.iter_mut().enumerate() {
-            if i != current_idx && !match_data.deck.is_empty() {
-                if let Some(card) = match_data.deck.pop() {
-                    player.cards.push(card);
-                    player.update_card_count();
-                }
-            }
+        // Validate: caller must be the host
+        if config.host != Some(caller) {
+            return Err(LinotError::OnlyHostCanAddBots);
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    // Tests will be added in future iteration
+        // Validate: ranked results only count games between people
+        if config.is_ranked {
+            return Err(LinotError::BotsNotAllowed);
+        }
+
+        // Validate: match must be waiting
+        if match_data.status != MatchStatus::Waiting {
+            return Err(LinotError::MatchAlreadyStarted);
+        }
+        self.check_lobby_open(&match_data)?;
+
+        // Validate: not at max players
+        if match_data.players.len() >= config.max_players as usize {
+            return Err(LinotError::MatchFull(config.max_players));
+        }
+
+        // Validate: bots never take a reserved seat
+        if match_data.players.len() + Self::held_seats(&config, &match_data) >= config.max_players as usize {
+            return Err(LinotError::SeatReserved);
+        }
+
+        // Seats shift when a lobby player leaves, so number the bot after
+        // the first bot identity not already at the table
+        let seat = match_data.players.len();
+        let bot = (0..config.max_players as usize)
+            .map(|number| Player::new_bot(number, difficulty))
+            .find(|bot| !match_data.players.iter().any(|p| p.owner == bot.owner))
+            .expect("fewer bots than seats");
+        match_data.players.push(bot);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::PlayerJoined,
+        ));
+
+        // A bot may fill the last seat of a table that is otherwise ready
+        if Self::should_auto_start(&config, &match_data) {
+            self.begin_match(&mut match_data)?;
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player toggling ready in the lobby
+    async fn handle_set_ready(&mut self, caller: AccountOwner, ready: bool) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+        let config = self.state.config.get().clone();
+
+        // Validate: match must be waiting
+        if match_data.status != MatchStatus::Waiting {
+            return Err(LinotError::MatchAlreadyStarted);
+        }
+        self.check_lobby_open(&match_data)?;
+
+        let seat = match_data
+            .players
+            .iter()
+            .position(|p| p.owner == caller)
+            .ok_or(LinotError::NotAPlayer)?;
+        match_data.players[seat].ready = ready;
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::ReadyChanged,
+        ));
+
+        if Self::should_auto_start(&config, &match_data) {
+            self.begin_match(&mut match_data)?;
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle anyone cancelling a table left waiting past its lobby expiry
+    async fn handle_expire_lobby(&mut self) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        if !self.lobby_expired(&match_data) {
+            return Err(LinotError::LobbyNotExpired);
+        }
+
+        match_data.status = MatchStatus::Cancelled;
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            None,
+            MatchEventKind::MatchCancelled,
+        ));
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// When a waiting table may be cancelled, if it has a lobby expiry
+    fn lobby_deadline(&self, match_data: &MatchData) -> Option<u64> {
+        let secs = self.state.config.get().lobby_expiry_secs?;
+        (match_data.status == MatchStatus::Waiting)
+            .then(|| match_data.created_at.saturating_add(secs.saturating_mul(1_000_000)))
+    }
+
+    /// Whether a waiting table has outlived its configured lobby expiry
+    fn lobby_expired(&mut self, match_data: &MatchData) -> bool {
+        self.lobby_deadline(match_data)
+            .is_some_and(|deadline| self.runtime.system_time().micros() >= deadline)
+    }
+
+    /// Reject lobby actions on a table that should be cancelled instead
+    fn check_lobby_open(&mut self, match_data: &MatchData) -> Result<(), LinotError> {
+        if self.lobby_expired(match_data) {
+            return Err(LinotError::LobbyExpired);
+        }
+        Ok(())
+    }
+
+    /// Whether an auto-start table is full and every seated player is ready
+    fn should_auto_start(config: &MatchConfig, match_data: &MatchData) -> bool {
+        config.auto_start
+            && match_data.status == MatchStatus::Waiting
+            && match_data.players.len() == config.max_players as usize
+            && match_data.players.iter().all(|p| p.ready)
+    }
+
+    /// Handle starting the match
+    async fn handle_start_match(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        // Validate: caller must be a player
+        if !match_data.players.iter().any(|p| p.owner == caller) {
+            return Err(LinotError::OnlyHostCanStart);
+        }
+
+        // For single-player demo: Allow starting with just 1 player
+        // (add bot seats with AddBot for an on-chain opponent)
+        if match_data.players.is_empty() {
+            return Err(LinotError::NotEnoughPlayers(1));
+        }
+
+        // Validate: match is waiting
+        if match_data.status != MatchStatus::Waiting {
+            return Err(LinotError::MatchAlreadyStarted);
+        }
+        self.check_lobby_open(&match_data)?;
+
+        // Validate: everyone else has confirmed they are present; starting
+        // confirms the caller
+        if let Some(player) = match_data.players.iter_mut().find(|p| p.owner == caller) {
+            player.ready = true;
+        }
+        let not_ready = match_data
+            .players
+            .iter()
+            .filter(|p| p.is_active && !p.ready)
+            .count();
+        if not_ready > 0 {
+            return Err(LinotError::PlayersNotReady(not_ready));
+        }
+
+        self.begin_match(&mut match_data)?;
+        self.state.match_data.set(match_data);
+        
+        Ok(())
+    }
+
+    /// Check the table layout, seat reserved players and deal
+    ///
+    /// Shared by `StartMatch` and auto-start.
+    fn begin_match(&mut self, match_data: &mut MatchData) -> Result<(), LinotError> {
+        // Validate: partnerships are two teams of two
+        let config = self.state.config.get().clone();
+        if config.team_mode && match_data.players.len() != 4 {
+            return Err(LinotError::TeamModeNeedsPlayers(4));
+        }
+        if config.team_mode && config.play_out {
+            return Err(LinotError::PlayOutWithTeams);
+        }
+        match_data.team_mode = config.team_mode;
+        match_data.partner_can_defend = config.partner_can_defend;
+        match_data.play_out = config.play_out;
+
+        // Reserved players take their seats; everyone else keeps join order
+        let players = std::mem::take(&mut match_data.players);
+        match_data.players = Self::seat_reserved_players(&config.reserved_seats, players);
+
+        let seed = self.match_seed(match_data);
+        let mut events = Vec::new();
+        GameEngine::start_match(match_data, &seed, &mut events);
+        self.log_events(events);
+
+        // Bots may hold the opening turn
+        self.run_automatic_turns(match_data)
+    }
+
+    /// Handle playing a card
+    async fn handle_play_card(
+        &mut self,
+        caller: AccountOwner,
+        card_id: u32,
+        chosen_suit: Option<CardSuit>,
+    ) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        GameEngine::check_turn(&match_data, caller)?;
+        self.play_card(&mut match_data, card_id, chosen_suit)?;
+        self.run_automatic_turns(&mut match_data)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle choosing the demanded suit after a Whot card
+    async fn handle_choose_suit(&mut self, caller: AccountOwner, suit: CardSuit) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        GameEngine::check_turn(&match_data, caller)?;
+        self.choose_suit(&mut match_data, suit)?;
+        self.run_automatic_turns(&mut match_data)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle drawing a card
+    async fn handle_draw_card(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        GameEngine::check_turn(&match_data, caller)?;
+        self.draw_card(&mut match_data)?;
+        self.run_automatic_turns(&mut match_data)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Play a card for the current player
+    fn play_card(
+        &mut self,
+        match_data: &mut MatchData,
+        card_id: u32,
+        chosen_suit: Option<CardSuit>,
+    ) -> Result<(), LinotError> {
+        let mut events = Vec::new();
+        GameEngine::play_card(match_data, card_id, chosen_suit, &mut events)?;
+        self.log_events(events);
+        Ok(())
+    }
+
+    /// Choose the demanded suit for the current player's Whot card
+    fn choose_suit(&mut self, match_data: &mut MatchData, suit: CardSuit) -> Result<(), LinotError> {
+        let mut events = Vec::new();
+        GameEngine::choose_suit(match_data, suit, &mut events)?;
+        self.log_events(events);
+        Ok(())
+    }
+
+    /// Draw for the current player (one card, or the pending penalty)
+    fn draw_card(&mut self, match_data: &mut MatchData) -> Result<(), LinotError> {
+        let seed = self.match_seed(match_data);
+        let mut events = Vec::new();
+        GameEngine::draw_card(match_data, &seed, &mut events)?;
+        self.log_events(events);
+        Ok(())
+    }
+
+    /// Shuffle seed of the match on this table
+    fn match_seed(&mut self, match_data: &MatchData) -> Vec<u8> {
+        let application_id = self.runtime.application_id().forget_abi();
+        GameEngine::match_seed(self.runtime.chain_id(), application_id, match_data.match_number)
+    }
+
+    /// Append events produced by the engine to the match log
+    fn log_events(&mut self, events: Vec<MatchEvent>) {
+        for event in events {
+            self.state.events.push(event);
+        }
+    }
+
+    /// Play bot turns and standing orders until a human decision is needed
+    /// or the match ends
+    fn run_automatic_turns(&mut self, match_data: &mut MatchData) -> Result<(), LinotError> {
+        self.settle_unattended(match_data);
+
+        for _ in 0..MAX_AUTOMATIC_MOVES {
+            if match_data.status != MatchStatus::InProgress
+                || match_data.turn_phase != TurnPhase::Play
+            {
+                break;
+            }
+            let seat = match_data.current_player_index;
+            let player = &match_data.players[seat];
+            let automatic = match player.bot {
+                Some(difficulty) => Some(bot::choose_move(match_data, seat, difficulty)),
+                // Away seats draw (or take the penalty) so the table keeps
+                // moving, unless the table pauses for them
+                None if player.away_since.is_some() => {
+                    (!self.state.config.get().pause_when_away).then_some(BotMove::Draw)
+                }
+                None => bot::standing_order_move(match_data, seat),
+            };
+            let Some(automatic) = automatic else {
+                break;
+            };
+
+            match automatic {
+                BotMove::Play { card_id, chosen_suit } => {
+                    self.play_card(match_data, card_id, chosen_suit)?
+                }
+                BotMove::Draw => self.draw_card(match_data)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Settle a match by hand totals once no human is left in the rotation
+    ///
+    /// Bots only move when an operation comes in, so a table of bots alone
+    /// would otherwise stall after `MAX_AUTOMATIC_MOVES`. A table nobody is
+    /// left playing at (not even a bot) has no one to award and is cancelled.
+    fn settle_unattended(&mut self, match_data: &mut MatchData) {
+        let rotation: Vec<usize> = (0..match_data.players.len())
+            .filter(|&seat| GameEngine::in_rotation(match_data, seat))
+            .collect();
+        let attended = rotation.iter().any(|&seat| match_data.players[seat].bot.is_none());
+        if match_data.status != MatchStatus::InProgress || attended {
+            return;
+        }
+
+        if rotation.is_empty() {
+            match_data.status = MatchStatus::Cancelled;
+            match_data.turn_phase = TurnPhase::Play;
+            match_data.pending_penalty = 0;
+            match_data.penalty_source = None;
+            self.state.events.push(MatchEvent::new(
+                match_data.version + 1,
+                None,
+                MatchEventKind::MatchCancelled,
+            ));
+            return;
+        }
+
+        let result = GameEngine::settle_by_hand_totals(match_data);
+        let mut events = Vec::new();
+        GameEngine::finish_match(match_data, result, &mut events);
+        self.log_events(events);
+    }
+
+    /// Handle calling last card
+    async fn handle_call_last_card(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        if match_data.status == MatchStatus::Paused {
+            return Err(LinotError::MatchPaused);
+        }
+
+        if let Some(idx) = match_data.players.iter().position(|p| p.owner == caller) {
+            match_data.players[idx].called_last_card = true;
+            self.state.events.push(MatchEvent::new(
+                match_data.version + 1,
+                Some(idx),
+                MatchEventKind::LastCardCalled,
+            ));
+        }
+
+        self.state.match_data.set(match_data);
+        
+        Ok(())
+    }
+
+    /// Handle a player updating their standing orders
+    async fn handle_set_auto_actions(&mut self, caller: AccountOwner, orders: AutoActions) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        let player = match_data
+            .players
+            .iter_mut()
+            .find(|p| p.owner == caller)
+            .ok_or(LinotError::NotAPlayer)?;
+        player.auto_actions = orders;
+
+        // The new orders may already apply if it is this player's turn
+        if match_data.status == MatchStatus::InProgress {
+            self.run_automatic_turns(&mut match_data)?;
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle challenging a player who didn't call last card
+    async fn handle_challenge_last_card(&mut self, caller: AccountOwner, player_index: usize) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        // Validate: only a seated player, while the hands are still in play
+        if match_data.status == MatchStatus::Paused {
+            return Err(LinotError::MatchPaused);
+        }
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+        Self::active_seat(&match_data, caller)?;
+
+        // Validate player index
+        if player_index >= match_data.players.len() {
+            return Err(LinotError::InvalidPlayerIndex(player_index));
+        }
+
+        let player = &mut match_data.players[player_index];
+
+        // If player has 1 card and didn't call last card, penalty
+        if player.card_count == 1 && !player.called_last_card {
+            // Draw 2 cards as penalty
+            self.state.events.push(
+                MatchEvent::new(match_data.version + 1, Some(player_index), MatchEventKind::PenaltyApplied)
+                    .with_penalty(2),
+            );
+            let mut drawn = Vec::new();
+            for _ in 0..2 {
+                if let Some(card) = match_data.deck.pop() {
+                    drawn.push(card);
+                }
+            }
+            self.state.events.push(
+                MatchEvent::new(match_data.version + 1, Some(player_index), MatchEventKind::CardsDrawn)
+                    .with_cards(&drawn),
+            );
+            player.cards.extend(drawn);
+            player.update_card_count();
+        }
+
+        self.state.match_data.set(match_data);
+        
+        Ok(())
+    }
+
+    /// Handle player leaving/forfeiting
+    async fn handle_leave_match(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        // A settled match keeps its seats and result as they are
+        if matches!(match_data.status, MatchStatus::Finished | MatchStatus::Cancelled) {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        if let Some(idx) = match_data.players.iter().position(|p| p.owner == caller) {
+            if match_data.status == MatchStatus::Waiting {
+                // Nothing is dealt yet: free the seat (with its ready flag,
+                // session key and nickname) so it can be taken again
+                match_data.players.remove(idx);
+                self.state.events.push(MatchEvent::new(
+                    match_data.version + 1,
+                    Some(idx),
+                    MatchEventKind::PlayerLeft,
+                ));
+            } else {
+                self.forfeit_seat(&mut match_data, idx)?;
+            }
+        }
+
+        self.state.match_data.set(match_data);
+        
+        Ok(())
+    }
+
+    /// Handle a player stepping away from their seat
+    async fn handle_step_away(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        let seat = Self::active_seat(&match_data, caller)?;
+        self.mark_away(&mut match_data, seat)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player marking the idle turn holder away
+    async fn handle_mark_away(&mut self, caller: AccountOwner, player_index: usize) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+        let idle_turn_secs = self.state.config.get().idle_turn_secs;
+
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        // Validate: caller is seated
+        Self::active_seat(&match_data, caller)?;
+
+        // Validate: only the turn holder, and only once they have idled
+        if player_index >= match_data.players.len() {
+            return Err(LinotError::InvalidPlayerIndex(player_index));
+        }
+        if player_index != match_data.current_player_index {
+            return Err(LinotError::NotTurnHolder(player_index));
+        }
+        let idle_since = match_data.turn_started_at.saturating_add(idle_turn_secs.saturating_mul(1_000_000));
+        if self.runtime.system_time().micros() < idle_since {
+            return Err(LinotError::TurnNotIdle);
+        }
+
+        self.mark_away(&mut match_data, player_index)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle an away player coming back to their seat
+    async fn handle_rejoin(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        let seat = Self::active_seat(&match_data, caller)?;
+        if match_data.players[seat].away_since.take().is_none() {
+            return Err(LinotError::NotAway);
+        }
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::PlayerRejoined,
+        ));
+
+        // Coming back on your own turn restarts its idle clock
+        if match_data.current_player_index == seat {
+            match_data.turn_started_at = self.runtime.system_time().micros();
+        }
+
+        // Bots and away seats may have stopped short of this seat's turn
+        if match_data.status == MatchStatus::InProgress {
+            self.run_automatic_turns(&mut match_data)?;
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player forfeiting an away seat whose grace period has ended
+    async fn handle_forfeit_away(&mut self, caller: AccountOwner, player_index: usize) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+        let grace_period_secs = self.state.config.get().grace_period_secs;
+
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        // Validate: caller is seated
+        Self::active_seat(&match_data, caller)?;
+
+        // Validate: the seat is away and its grace period is over
+        let player = match_data
+            .players
+            .get(player_index)
+            .ok_or(LinotError::InvalidPlayerIndex(player_index))?;
+        let away_since = player
+            .away_since
+            .filter(|_| player.is_active)
+            .ok_or(LinotError::NotAway)?;
+        let grace_end = away_since.saturating_add(grace_period_secs.saturating_mul(1_000_000));
+        if self.runtime.system_time().micros() < grace_end {
+            return Err(LinotError::GracePeriodRunning);
+        }
+
+        self.forfeit_seat(&mut match_data, player_index)?;
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player requesting a pause, or agreeing to a pending request
+    async fn handle_pause_vote(&mut self, caller: AccountOwner, request: bool) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        // Validate: match is in progress
+        if match_data.status == MatchStatus::Paused {
+            return Err(LinotError::MatchPaused);
+        }
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        let seat = Self::active_seat(&match_data, caller)?;
+        if !request && match_data.pause_votes.is_empty() {
+            return Err(LinotError::NoPauseRequested);
+        }
+        if match_data.pause_votes.contains(&seat) {
+            return Err(LinotError::AlreadyAgreed);
+        }
+        match_data.pause_votes.push(seat);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::PauseRequested,
+        ));
+
+        if Self::everyone_agrees(&match_data) {
+            match_data.status = MatchStatus::Paused;
+            match_data.paused_at = Some(self.runtime.system_time().micros());
+            match_data.pause_votes.clear();
+            self.state.events.push(MatchEvent::new(
+                match_data.version + 1,
+                None,
+                MatchEventKind::MatchPaused,
+            ));
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player agreeing to resume a paused match
+    async fn handle_resume(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        if match_data.status != MatchStatus::Paused {
+            return Err(LinotError::MatchNotPaused);
+        }
+
+        let seat = Self::active_seat(&match_data, caller)?;
+        if match_data.pause_votes.contains(&seat) {
+            return Err(LinotError::AlreadyAgreed);
+        }
+        match_data.pause_votes.push(seat);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::ResumeRequested,
+        ));
+
+        if Self::everyone_agrees(&match_data) {
+            // Timers were frozen: move them forward by the time spent paused
+            let now = self.runtime.system_time().micros();
+            let paused_for = now.saturating_sub(match_data.paused_at.take().unwrap_or(now));
+            match_data.turn_started_at = match_data.turn_started_at.saturating_add(paused_for);
+            for player in match_data.players.iter_mut() {
+                if let Some(since) = player.away_since.as_mut() {
+                    *since = since.saturating_add(paused_for);
+                }
+            }
+
+            match_data.status = MatchStatus::InProgress;
+            match_data.pause_votes.clear();
+            self.state.events.push(MatchEvent::new(
+                match_data.version + 1,
+                None,
+                MatchEventKind::MatchResumed,
+            ));
+        }
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle a player asking to take back the move they just made
+    async fn handle_request_takeback(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        // Validate: casual matches only
+        if self.state.config.get().is_ranked {
+            return Err(LinotError::TakebacksDisabled);
+        }
+        if match_data.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        // Validate: the caller made the last move and nothing happened since
+        let seat = Self::active_seat(&match_data, caller)?;
+        if self.state.takeback.get().as_ref().map(|point| point.seat) != Some(seat) {
+            return Err(LinotError::NoTakeback);
+        }
+        if match_data.takeback_request.is_some() {
+            return Err(LinotError::AlreadyAgreed);
+        }
+
+        match_data.takeback_request = Some(seat);
+        match_data.takeback_votes.clear();
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::TakebackRequested,
+        ));
+
+        // With only bots (or nobody) left to ask, the takeback applies at once
+        self.apply_takeback_if_agreed(&mut match_data);
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle an opponent agreeing to the pending takeback
+    async fn handle_accept_takeback(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        let requester = match_data.takeback_request.ok_or(LinotError::NoTakebackRequested)?;
+        let seat = Self::active_seat(&match_data, caller)?;
+        if seat == requester || match_data.takeback_votes.contains(&seat) {
+            return Err(LinotError::AlreadyAgreed);
+        }
+
+        match_data.takeback_votes.push(seat);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::TakebackAccepted,
+        ));
+        self.apply_takeback_if_agreed(&mut match_data);
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Handle an opponent refusing the pending takeback (or its requester withdrawing it)
+    async fn handle_decline_takeback(&mut self, caller: AccountOwner) -> Result<(), LinotError> {
+        let mut match_data = self.state.match_data.get().clone();
+
+        if match_data.takeback_request.is_none() {
+            return Err(LinotError::NoTakebackRequested);
+        }
+        let seat = Self::active_seat(&match_data, caller)?;
+
+        // A refused move is final
+        match_data.takeback_request = None;
+        match_data.takeback_votes.clear();
+        self.state.takeback.set(None);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::TakebackDeclined,
+        ));
+
+        self.state.match_data.set(match_data);
+
+        Ok(())
+    }
+
+    /// Revert the last move once every active human opponent has agreed
+    fn apply_takeback_if_agreed(&mut self, match_data: &mut MatchData) {
+        let Some(requester) = match_data.takeback_request else {
+            return;
+        };
+        let agreed = match_data
+            .players
+            .iter()
+            .enumerate()
+            .filter(|&(seat, p)| seat != requester && p.is_active && p.bot.is_none())
+            .all(|(seat, _)| match_data.takeback_votes.contains(&seat));
+        if !agreed {
+            return;
+        }
+
+        let Some(point) = self.state.takeback.get().clone() else {
+            return;
+        };
+        let mut events = Vec::new();
+        GameEngine::revert(match_data, point.before, point.seat, &mut events);
+        self.log_events(events);
+        self.state.takeback.set(None);
+    }
+
+    /// Keep the state before a move so its player can ask to take it back
+    fn record_takeback_point(&mut self, caller: AccountOwner, before: MatchData) {
+        let Some(seat) = before.players.iter().position(|p| p.owner == caller) else {
+            return;
+        };
+
+        // A new move supersedes any takeback still being discussed
+        let match_data = self.state.match_data.get_mut();
+        match_data.takeback_request = None;
+        match_data.takeback_votes.clear();
+
+        self.state.takeback.set(Some(TakebackPoint { seat, before }));
+    }
+
+    /// Forget the takeback point once anything but a move or a takeback vote happens
+    fn clear_takeback(&mut self) {
+        if self.state.takeback.get().is_some() {
+            self.state.takeback.set(None);
+        }
+        if self.state.match_data.get().takeback_request.is_some() {
+            let match_data = self.state.match_data.get_mut();
+            match_data.takeback_request = None;
+            match_data.takeback_votes.clear();
+        }
+    }
+
+    /// Whether every active human player has voted (bots always agree)
+    fn everyone_agrees(match_data: &MatchData) -> bool {
+        match_data
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_active && p.bot.is_none())
+            .all(|(seat, _)| match_data.pause_votes.contains(&seat))
+    }
+
+    /// Seat of a caller still playing at this table
+    fn active_seat(match_data: &MatchData, caller: AccountOwner) -> Result<usize, LinotError> {
+        match_data
+            .players
+            .iter()
+            .position(|p| p.owner == caller && p.is_active)
+            .ok_or(LinotError::NotAPlayer)
+    }
+
+    /// Mark a seat away, holding it for the grace period
+    fn mark_away(&mut self, match_data: &mut MatchData, seat: usize) -> Result<(), LinotError> {
+        let now = self.runtime.system_time().micros();
+        let player = &mut match_data.players[seat];
+        if player.away_since.is_some() {
+            return Err(LinotError::AlreadyAway);
+        }
+        player.away_since = Some(now);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(seat),
+            MatchEventKind::PlayerAway,
+        ));
+
+        // The away seat may hold the turn
+        self.run_automatic_turns(match_data)
+    }
+
+    /// Remove a seat from the match for good, awarding it if one side remains
+    fn forfeit_seat(&mut self, match_data: &mut MatchData, idx: usize) -> Result<(), LinotError> {
+        // A paused match is settled as if in progress, then stays paused
+        // for everyone else unless the forfeit ended it
+        let paused = match_data.status == MatchStatus::Paused;
+        if paused {
+            match_data.status = MatchStatus::InProgress;
+        }
+
+        // Mark player as inactive
+        match_data.players[idx].is_active = false;
+        match_data.left_order.push(idx);
+        self.state.events.push(MatchEvent::new(
+            match_data.version + 1,
+            Some(idx),
+            MatchEventKind::PlayerLeft,
+        ));
+
+        // Pass the turn on if the leaver held it
+        if match_data.status == MatchStatus::InProgress && idx == match_data.current_player_index {
+            match_data.turn_phase = TurnPhase::Play;
+            GameEngine::advance_turn(match_data);
+        }
+
+        // Check if only one active player (or one team) is left
+        let active_players: Vec<_> = match_data.players.iter().filter(|p| p.is_active).collect();
+        let active_teams: Vec<u8> = (0..match_data.players.len())
+            .filter(|&i| match_data.players[i].is_active)
+            .filter_map(|i| GameEngine::team_of(match_data, i))
+            .collect();
+        if match_data.team_mode
+            && match_data.status == MatchStatus::InProgress
+            && active_teams.windows(2).all(|pair| pair[0] == pair[1])
+        {
+            // Remaining team wins
+            let team = active_teams.first().copied().unwrap_or_default();
+            let players = GameEngine::team_members(match_data, team);
+            let mut events = Vec::new();
+            GameEngine::finish_match(match_data, GameResult::TeamWinner { team, players }, &mut events);
+            self.log_events(events);
+        } else if match_data.play_out && match_data.status == MatchStatus::InProgress {
+            // Play-out continues until one player is left in the rotation
+            if let Some(result) = GameEngine::check_game_end(match_data) {
+                let mut events = Vec::new();
+                GameEngine::finish_match(match_data, result, &mut events);
+                self.log_events(events);
+            }
+        } else if !match_data.team_mode
+            && match_data.status == MatchStatus::InProgress
+            && active_players.len() == 1
+        {
+            // Remaining player wins
+            let winner_idx = match_data.players.iter().position(|p| p.is_active).unwrap();
+            let mut events = Vec::new();
+            GameEngine::finish_match(match_data, GameResult::Winner(winner_idx), &mut events);
+            self.log_events(events);
+        }
+        self.settle_unattended(match_data);
+
+        if paused {
+            if match_data.status == MatchStatus::InProgress {
+                match_data.status = MatchStatus::Paused;
+            } else {
+                match_data.paused_at = None;
+                match_data.pause_votes.clear();
+            }
+        }
+
+        // The turn may have passed to a bot or another away seat
+        self.run_automatic_turns(match_data)
+    }
+
+    /// Handle remote player join (cross-chain)
+    async fn handle_remote_join(&mut self, player: AccountOwner, nickname: String) -> Result<(), LinotError> {
+        // In V1, we treat this the same as local join (no invite code travels
+        // with the message, so private tables need an allowlist or reservation)
+        self.handle_join_match(player, Some(nickname), None, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linera_sdk::{
+        linera_base_types::{ApplicationId, Timestamp},
+        util::BlockingWait,
+    };
+
+    fn owner(seat: u8) -> AccountOwner {
+        AccountOwner::Reserved(seat)
+    }
+
+    fn create_table(max_players: u8) -> LinotContract {
+        let runtime = ContractRuntime::new()
+            .with_application_parameters(())
+            .with_chain_id("0".repeat(64).parse().unwrap())
+            .with_application_id(ApplicationId::new("1".repeat(64).parse().unwrap()).with_abi())
+            .with_authenticated_signer(owner(0))
+            .with_system_time(Timestamp::from(0));
+        let mut contract = LinotContract {
+            state: LinotState::load(runtime.root_view_storage_context())
+                .blocking_wait()
+                .expect("Failed to read from mock key value store"),
+            runtime,
+        };
+        let config = MatchConfig {
+            max_players,
+            ..MatchConfig::default()
+        };
+        contract.instantiate(config).blocking_wait();
+        contract
+    }
+
+    fn execute(contract: &mut LinotContract, caller: AccountOwner, operation: Operation) {
+        contract.runtime.set_authenticated_signer(Some(caller));
+        contract.execute_operation(operation).blocking_wait();
+    }
+
+    fn join(contract: &mut LinotContract, caller: AccountOwner, nickname: &str) {
+        let operation = Operation::JoinMatch {
+            nickname: Some(nickname.to_string()),
+            invite_code: None,
+            session: None,
+            expected_version: None,
+        };
+        execute(contract, caller, operation);
+    }
+
+    fn leave(contract: &mut LinotContract, caller: AccountOwner) {
+        execute(contract, caller, Operation::LeaveMatch { expected_version: None });
+    }
+
+    /// Two players seated at a table the host has started
+    fn started_duel() -> LinotContract {
+        let mut contract = create_table(2);
+        join(&mut contract, owner(0), "Alice");
+        join(&mut contract, owner(1), "Bob");
+        let ready = Operation::SetReady {
+            ready: true,
+            expected_version: None,
+        };
+        execute(&mut contract, owner(1), ready);
+        execute(&mut contract, owner(0), Operation::StartMatch { expected_version: None });
+        assert_eq!(contract.state.match_data.get().status, MatchStatus::InProgress);
+        contract
+    }
+
+    #[test]
+    fn table_left_to_bots_is_settled() {
+        let mut contract = create_table(3);
+        join(&mut contract, owner(0), "Alice");
+        for _ in 0..2 {
+            let add_bot = Operation::AddBot {
+                difficulty: BotDifficulty::Easy,
+                expected_version: None,
+            };
+            execute(&mut contract, owner(0), add_bot);
+        }
+        execute(&mut contract, owner(0), Operation::StartMatch { expected_version: None });
+        assert_eq!(contract.state.match_data.get().status, MatchStatus::InProgress);
+
+        leave(&mut contract, owner(0));
+
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::Finished);
+        let result = GameEngine::match_result(match_data).unwrap();
+        assert!(!result.winners.contains(&owner(0)));
+    }
+
+    #[test]
+    fn rejoining_moves_a_stalled_bot() {
+        let mut contract = create_table(2);
+        join(&mut contract, owner(0), "Alice");
+        let add_bot = Operation::AddBot {
+            difficulty: BotDifficulty::Easy,
+            expected_version: None,
+        };
+        execute(&mut contract, owner(0), add_bot);
+        execute(&mut contract, owner(0), Operation::StartMatch { expected_version: None });
+
+        // Left on the bot's turn, as after running out of automatic moves
+        let mut match_data = contract.state.match_data.get().clone();
+        match_data.current_player_index = 1;
+        match_data.turn_phase = TurnPhase::Play;
+        match_data.players[0].away_since = Some(0);
+        contract.state.match_data.set(match_data);
+        let logged = contract.state.events.count();
+
+        execute(&mut contract, owner(0), Operation::Rejoin { expected_version: None });
+
+        assert_eq!(contract.state.match_data.get().players[0].away_since, None);
+        let events = contract
+            .state
+            .events
+            .read(logged..contract.state.events.count())
+            .blocking_wait()
+            .unwrap();
+        assert!(events.iter().any(|event| {
+            event.player_index == Some(1)
+                && matches!(event.kind, MatchEventKind::CardPlayed | MatchEventKind::CardsDrawn)
+        }));
+    }
+
+    #[test]
+    fn sole_human_leaving_a_table_with_no_bots_cancels_it() {
+        let mut contract = create_table(2);
+        join(&mut contract, owner(0), "Alice");
+        execute(&mut contract, owner(0), Operation::StartMatch { expected_version: None });
+        assert_eq!(contract.state.match_data.get().status, MatchStatus::InProgress);
+
+        leave(&mut contract, owner(0));
+
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::Cancelled);
+        assert!(GameEngine::audit(match_data).is_empty());
+        assert!(GameEngine::match_result(match_data).is_none());
+        assert_eq!(contract.public_state().left_order, vec![owner(0)]);
+    }
+
+    #[test]
+    fn pause_needs_everyone_and_so_does_resuming() {
+        let mut contract = started_duel();
+
+        execute(&mut contract, owner(1), Operation::RequestPause { expected_version: None });
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::InProgress);
+        assert_eq!(match_data.pause_votes, vec![1]);
+
+        execute(&mut contract, owner(0), Operation::AcceptPause { expected_version: None });
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::Paused);
+        assert!(match_data.pause_votes.is_empty());
+
+        execute(&mut contract, owner(0), Operation::Resume { expected_version: None });
+        assert_eq!(contract.state.match_data.get().status, MatchStatus::Paused);
+        execute(&mut contract, owner(1), Operation::Resume { expected_version: None });
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::InProgress);
+        assert!(match_data.pause_votes.is_empty());
+        assert_eq!(match_data.paused_at, None);
+    }
+
+    #[test]
+    fn pause_request_lapses_once_play_moves_on() {
+        let mut contract = started_duel();
+        execute(&mut contract, owner(1), Operation::RequestPause { expected_version: None });
+
+        execute(&mut contract, owner(0), Operation::DrawCard { expected_version: None });
+
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.current_player_index, 1);
+        assert!(match_data.pause_votes.is_empty());
+        let stale_accept = contract.handle_pause_vote(owner(0), false).blocking_wait();
+        assert!(matches!(stale_accept, Err(LinotError::NoPauseRequested)));
+    }
+
+    #[test]
+    fn team_mode_needs_four_seats_and_no_play_out() {
+        let teams = MatchConfig {
+            max_players: 4,
+            team_mode: true,
+            ..MatchConfig::default()
+        };
+        assert!(LinotContract::validate_config(&teams).is_ok());
+
+        let three_seats = MatchConfig {
+            max_players: 3,
+            ..teams.clone()
+        };
+        assert!(matches!(
+            LinotContract::validate_config(&three_seats),
+            Err(LinotError::TeamModeNeedsPlayers(4))
+        ));
+
+        let play_out = MatchConfig {
+            play_out: true,
+            ..teams
+        };
+        assert!(matches!(
+            LinotContract::validate_config(&play_out),
+            Err(LinotError::PlayOutWithTeams)
+        ));
+    }
+
+    #[test]
+    fn leaving_a_waiting_table_does_not_finish_it() {
+        let mut contract = create_table(3);
+        join(&mut contract, owner(0), "Alice");
+        join(&mut contract, owner(1), "Bob");
+
+        let ready = Operation::SetReady {
+            ready: true,
+            expected_version: None,
+        };
+        execute(&mut contract, owner(1), ready);
+
+        leave(&mut contract, owner(1));
+
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::Waiting);
+        assert_eq!(match_data.winner_index, None);
+        assert!(GameEngine::match_result(match_data).is_none());
+        assert_eq!(match_data.players.len(), 1);
+        assert!(match_data.players.iter().all(|p| p.owner != owner(1)));
+
+        // The seat, its nickname and the owner are all free to come back
+        join(&mut contract, owner(1), "Bob");
+        join(&mut contract, owner(2), "Carol");
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.players.len(), 3);
+        assert_eq!(match_data.players[1].owner, owner(1));
+        assert!(!match_data.players[1].ready);
+    }
+
+    #[test]
+    fn bots_keep_distinct_identities_after_a_lobby_seat_is_freed() {
+        let mut contract = create_table(3);
+        join(&mut contract, owner(0), "Alice");
+        join(&mut contract, owner(1), "Bob");
+        let add_bot = || Operation::AddBot {
+            difficulty: BotDifficulty::Easy,
+            expected_version: None,
+        };
+        execute(&mut contract, owner(0), add_bot());
+
+        leave(&mut contract, owner(1));
+        execute(&mut contract, owner(0), add_bot());
+
+        let players = &contract.state.match_data.get().players;
+        assert_eq!(players.len(), 3);
+        assert_ne!(players[1].owner, players[2].owner);
+        assert_ne!(players[1].nickname, players[2].nickname);
+    }
+
+    #[test]
+    fn leaving_a_match_in_progress_awards_the_last_player() {
+        let mut contract = started_duel();
+
+        leave(&mut contract, owner(0));
+
+        let result = GameEngine::match_result(contract.state.match_data.get()).unwrap();
+        assert_eq!(result.winners, vec![owner(1)]);
+    }
+
+    #[test]
+    fn leaving_a_finished_match_keeps_the_result() {
+        let mut contract = started_duel();
+        leave(&mut contract, owner(0));
+        let result = GameEngine::match_result(contract.state.match_data.get()).unwrap();
+
+        let leave_again = contract.handle_leave_match(owner(1)).blocking_wait();
+
+        assert!(matches!(leave_again, Err(LinotError::MatchNotInProgress)));
+        let match_data = contract.state.match_data.get();
+        assert_eq!(match_data.status, MatchStatus::Finished);
+        assert!(match_data.players[1].is_active);
+        assert_eq!(GameEngine::match_result(match_data), Some(result));
+    }
+
+    #[test]
+    fn last_card_challenges_need_a_seated_challenger_and_a_live_match() {
+        let mut contract = started_duel();
+
+        let outsider = contract.handle_challenge_last_card(owner(2), 1).blocking_wait();
+        assert!(matches!(outsider, Err(LinotError::NotAPlayer)));
+
+        leave(&mut contract, owner(0));
+        let tally = contract.state.match_data.get().final_tally.clone();
+
+        let after_finish = contract.handle_challenge_last_card(owner(1), 1).blocking_wait();
+
+        assert!(matches!(after_finish, Err(LinotError::MatchNotInProgress)));
+        assert_eq!(contract.state.match_data.get().final_tally, tally);
+    }
 }
@@ -1,11 +1,15 @@
-use crate::state::MatchData;
-use linot::{Card, CardSuit, CardValue};
+use crate::state::{MatchData, MatchEvent, MatchEventKind, MatchStatus, TurnPhase};
+use linera_sdk::linera_base_types::{AccountOwner, ApplicationId, ChainId};
+use linot::{Card, CardSuit, CardValue, LinotError, MatchResult};
 
 /// Game engine for Whot/Linot card game logic
 pub struct GameEngine;
 
 impl GameEngine {
     /// Create a full 61-card Whot deck (56 regular + 5 Whot cards)
+    ///
+    /// Every card gets a stable ID (its position in the unshuffled deck) that
+    /// it keeps through dealing, drawing, discarding and reshuffling.
     pub fn create_deck() -> Vec<Card> {
         let mut deck = Vec::with_capacity(61);
 
@@ -40,13 +44,18 @@ impl GameEngine {
         // Create regular cards (multiple copies based on standard Whot distribution)
         for &suit in &suits {
             for &value in &values {
-                deck.push(Card { suit, value });
+                deck.push(Card {
+                    id: deck.len() as u32,
+                    suit,
+                    value,
+                });
             }
         }
 
         // Add 5 Whot (wild) cards
         for _ in 0..5 {
             deck.push(Card {
+                id: deck.len() as u32,
                 suit: CardSuit::Star, // Whot cards typically use Star suit visually
                 value: CardValue::Whot,
             });
@@ -55,8 +64,17 @@ impl GameEngine {
         deck
     }
 
+    /// Shuffle seed of a match: its table's chain and application, and how
+    /// many matches the table has set up before
+    ///
+    /// Hosting applications create many tables on one chain, and reuse them
+    /// for rematches, so the chain alone would keep repeating the same deal.
+    pub fn match_seed(chain_id: ChainId, application_id: ApplicationId, match_number: u32) -> Vec<u8> {
+        format!("{}:{}:{}", chain_id, application_id, match_number).into_bytes()
+    }
+
     /// Shuffle deck using deterministic seed derived from chain_id
-    pub fn shuffle_with_seed(deck: &mut Vec<Card>, seed: &[u8]) {
+    pub fn shuffle_with_seed(deck: &mut [Card], seed: &[u8]) {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
@@ -147,20 +165,22 @@ impl GameEngine {
             }
             SpecialEffect::PlayAgain => {
                 // Current player plays again (don't advance turn)
-                // This is handled in contract by not calling advance_turn()
+                // This is handled in play_card by not calling advance_turn()
             }
             SpecialEffect::DrawTwo => {
                 state.pending_penalty = 2;
+                state.penalty_source = Some(state.current_player_index);
             }
             SpecialEffect::DrawThree => {
                 state.pending_penalty = 3;
+                state.penalty_source = Some(state.current_player_index);
             }
             SpecialEffect::SkipNext => {
-                // Skip next player (handled by advancing turn in contract)
-                // The contract will advance turn twice: once here, once normally
+                // Skip next player (handled by advancing turn in play_card)
+                // play_card advances the turn twice instead of once
             }
             SpecialEffect::AllDrawOne => {
-                // All other players draw 1 card (handled in contract)
+                // All other players draw 1 card (handled in play_card)
             }
             SpecialEffect::None => {
                 // Clear active demand if no special effect
@@ -169,45 +189,823 @@ impl GameEngine {
         }
     }
 
-    /// Advance to next player's turn
+    /// Advance to next player's turn, skipping seats out of the rotation
     pub fn advance_turn(state: &mut MatchData) {
         let num_players = state.players.len();
-        if num_players > 0 {
+        for _ in 0..num_players {
             state.current_player_index = (state.current_player_index + 1) % num_players;
+            if Self::in_rotation(state, state.current_player_index) {
+                break;
+            }
+        }
+    }
+
+    /// Whether a seat still takes turns: it has not left, nor gone out in play-out
+    pub fn in_rotation(state: &MatchData, seat: usize) -> bool {
+        state.players.get(seat).is_some_and(|p| p.is_active) && !state.placements.contains(&seat)
+    }
+
+    /// Team of a seat in a partnership match (seats alternate teams)
+    pub fn team_of(state: &MatchData, seat: usize) -> Option<u8> {
+        state.team_mode.then_some((seat % 2) as u8)
+    }
+
+    /// Seats belonging to a team, in seat order
+    pub fn team_members(state: &MatchData, team: u8) -> Vec<usize> {
+        (0..state.players.len())
+            .filter(|&seat| Self::team_of(state, seat) == Some(team))
+            .collect()
+    }
+
+    /// Whether `seat` may answer the pending penalty with a card
+    ///
+    /// Always true unless the partnership variant forbids defending a
+    /// penalty laid by one's own partner.
+    pub fn may_defend(state: &MatchData, seat: usize) -> bool {
+        if state.partner_can_defend {
+            return true;
+        }
+        match (state.penalty_source, Self::team_of(state, seat)) {
+            (Some(source), Some(team)) => source == seat || Self::team_of(state, source) != Some(team),
+            _ => true,
+        }
+    }
+
+    /// Shuffle, deal the opening hands and turn the first discard
+    ///
+    /// `seed` must be identical on every validator (the contract uses `match_seed`).
+    pub fn start_match(state: &mut MatchData, seed: &[u8], events: &mut Vec<MatchEvent>) {
+        // Create and shuffle deck
+        let mut deck = Self::create_deck();
+        Self::shuffle_with_seed(&mut deck, seed);
+
+        // Deal initial hands (6 cards each)
+        let hands = Self::deal_initial_hands(&mut deck, state.players.len());
+        let next_version = state.version + 1;
+        for (i, player) in state.players.iter_mut().enumerate() {
+            player.cards = hands[i].clone();
+            player.update_card_count();
+            events.push(
+                MatchEvent::new(next_version, Some(i), MatchEventKind::CardsDealt)
+                    .with_cards(&player.cards),
+            );
+        }
+
+        // Place first card in discard pile
+        if let Some(first_card) = deck.pop() {
+            events.push(
+                MatchEvent::new(next_version, None, MatchEventKind::MatchStarted)
+                    .with_cards(std::slice::from_ref(&first_card)),
+            );
+            state.discard_pile.push(first_card);
+        }
+
+        // Update match state
+        state.deck = deck;
+        state.status = MatchStatus::InProgress;
+        state.current_player_index = 0;
+    }
+
+    /// Validate that the match is in progress and it is the caller's turn
+    pub fn check_turn(state: &MatchData, caller: AccountOwner) -> Result<(), LinotError> {
+        // Validate: match is in progress
+        if state.status == MatchStatus::Paused {
+            return Err(LinotError::MatchPaused);
+        }
+        if state.status != MatchStatus::InProgress {
+            return Err(LinotError::MatchNotInProgress);
+        }
+
+        // Validate: it's caller's turn
+        if state.players[state.current_player_index].owner != caller {
+            return Err(LinotError::NotYourTurn);
+        }
+
+        Ok(())
+    }
+
+    /// Play a card from the current player's hand
+    pub fn play_card(
+        state: &mut MatchData,
+        card_id: u32,
+        chosen_suit: Option<CardSuit>,
+        events: &mut Vec<MatchEvent>,
+    ) -> Result<(), LinotError> {
+        // Validate: no Whot suit choice is outstanding
+        if state.turn_phase != TurnPhase::Play {
+            return Err(LinotError::SuitChoicePending);
         }
+
+        // Validate: the penalty may be defended by this player
+        let current_idx = state.current_player_index;
+        if state.pending_penalty > 0 && !Self::may_defend(state, current_idx) {
+            return Err(LinotError::PartnerPenalty);
+        }
+
+        // Validate: card is in the player's hand
+        let current_player = &mut state.players[current_idx];
+        let card_index = current_player
+            .cards
+            .iter()
+            .position(|card| card.id == card_id)
+            .ok_or(LinotError::CardNotInHand(card_id))?;
+
+        // Get the card
+        let card = current_player.cards[card_index].clone();
+
+        // Validate: only a Whot card carries a suit choice
+        if chosen_suit.is_some() && card.value != CardValue::Whot {
+            return Err(LinotError::UnexpectedSuitChoice);
+        }
+
+        // Get top card from discard pile
+        let top_card = state
+            .discard_pile
+            .last()
+            .ok_or(LinotError::NoCardInDiscardPile)?;
+
+        // Validate: card can be played
+        if !Self::is_valid_play(
+            &card,
+            top_card,
+            state.active_shape_demand,
+            state.pending_penalty,
+        ) {
+            return Err(LinotError::InvalidCardPlay);
+        }
+
+        // Remove card from hand
+        current_player.cards.remove(card_index);
+        current_player.update_card_count();
+
+        // Add to discard pile
+        state.discard_pile.push(card.clone());
+        let mut played = MatchEvent::new(state.version + 1, Some(current_idx), MatchEventKind::CardPlayed)
+            .with_cards(std::slice::from_ref(&card));
+        if let Some(suit) = chosen_suit {
+            played = played.with_suit(suit);
+        }
+        events.push(played);
+
+        // Check if player should call last card
+        if current_player.card_count == 1
+            && !current_player.called_last_card
+            && current_player.auto_actions.auto_call_last_card
+        {
+            // Standing order (on by default, as in V1)
+            current_player.called_last_card = true;
+            events.push(MatchEvent::new(
+                state.version + 1,
+                Some(current_idx),
+                MatchEventKind::LastCardCalled,
+            ));
+        }
+
+        // Play-out: going out takes the next place and leaves the rotation
+        if state.play_out && state.players[current_idx].cards.is_empty() {
+            state.placements.push(current_idx);
+            events.push(MatchEvent::new(
+                state.version + 1,
+                Some(current_idx),
+                MatchEventKind::PlayerFinished,
+            ));
+        }
+
+        // Check if game ended: the tally is final, so no effect or turn change follows
+        if let Some(result) = Self::check_game_end(state) {
+            Self::finish_match(state, result, events);
+            return Ok(());
+        }
+
+        // Apply special card effect
+        let effect = Self::get_card_effect(&card);
+        Self::apply_effect(state, effect, chosen_suit);
+
+        // Whot played without a suit: keep the turn until ChooseSuit arrives,
+        // unless its player went out in play-out (no demand, the turn passes)
+        if effect == SpecialEffect::ChooseShape && chosen_suit.is_none() {
+            if Self::in_rotation(state, current_idx) {
+                state.turn_phase = TurnPhase::ChooseSuit;
+                return Ok(());
+            }
+            state.active_shape_demand = None;
+        }
+
+        // Handle General Market effect if needed
+        if let SpecialEffect::AllDrawOne = effect {
+            Self::apply_general_market(state, events);
+        }
+
+        // Advance turn based on effect
+        if effect == SpecialEffect::PlayAgain && Self::in_rotation(state, current_idx) {
+            // Hold On (1): Current player plays again, don't advance
+        } else if effect == SpecialEffect::SkipNext {
+            // Suspension (8): Skip next player by advancing twice
+            Self::advance_turn(state);
+            Self::advance_turn(state);
+        } else {
+            // Normal: Advance to next player
+            Self::advance_turn(state);
+        }
+
+        Ok(())
+    }
+
+    /// Choose the demanded suit for the current player's Whot card
+    pub fn choose_suit(
+        state: &mut MatchData,
+        suit: CardSuit,
+        events: &mut Vec<MatchEvent>,
+    ) -> Result<(), LinotError> {
+        // Validate: a Whot card is waiting for its suit
+        if state.turn_phase != TurnPhase::ChooseSuit {
+            return Err(LinotError::NoSuitChoicePending);
+        }
+
+        Self::apply_effect(state, SpecialEffect::ChooseShape, Some(suit));
+        state.turn_phase = TurnPhase::Play;
+        events.push(
+            MatchEvent::new(
+                state.version + 1,
+                Some(state.current_player_index),
+                MatchEventKind::SuitChosen,
+            )
+            .with_suit(suit),
+        );
+        Self::advance_turn(state);
+
+        Ok(())
+    }
+
+    /// Draw for the current player (one card, or the pending penalty)
+    ///
+    /// `seed` is extended with the round number whenever the discard pile
+    /// has to be reshuffled into the market.
+    pub fn draw_card(
+        state: &mut MatchData,
+        seed: &[u8],
+        events: &mut Vec<MatchEvent>,
+    ) -> Result<(), LinotError> {
+        // Validate: no Whot suit choice is outstanding
+        if state.turn_phase != TurnPhase::Play {
+            return Err(LinotError::SuitChoicePending);
+        }
+
+        // Determine how many cards to draw
+        let current_player_idx = state.current_player_index;
+        let is_penalty = state.pending_penalty > 0;
+        let cards_to_draw = if is_penalty {
+            let count = state.pending_penalty;
+            state.pending_penalty = 0;
+            state.penalty_source = None;
+            count
+        } else {
+            1
+        };
+
+        // Draw cards
+        let next_version = state.version + 1;
+        if is_penalty {
+            events.push(
+                MatchEvent::new(next_version, Some(current_player_idx), MatchEventKind::PenaltyApplied)
+                    .with_penalty(cards_to_draw),
+            );
+        }
+        let mut drawn = Vec::new();
+        for _ in 0..cards_to_draw {
+            if state.deck.is_empty() {
+                // Reshuffle discard pile (except top card)
+                if state.discard_pile.len() > 1 {
+                    let top_card = state.discard_pile.pop().unwrap();
+                    state.deck = state.discard_pile.clone();
+                    state.discard_pile.clear();
+                    state.discard_pile.push(top_card);
+                    events.push(
+                        MatchEvent::new(next_version, None, MatchEventKind::DeckReshuffled)
+                            .with_cards(&state.deck),
+                    );
+
+                    // Reshuffle with new seed
+                    state.round_number += 1;
+                    let round_seed = [seed, state.round_number.to_string().as_bytes()].concat();
+                    Self::shuffle_with_seed(&mut state.deck, &round_seed);
+                } else {
+                    break; // No more cards available
+                }
+            }
+
+            if let Some(card) = state.deck.pop() {
+                drawn.push(card);
+            }
+        }
+
+        events.push(
+            MatchEvent::new(next_version, Some(current_player_idx), MatchEventKind::CardsDrawn)
+                .with_cards(&drawn),
+        );
+        let current_player = &mut state.players[current_player_idx];
+        current_player.cards.extend(drawn);
+        current_player.update_card_count();
+
+        // Clear active shape demand after drawing
+        state.active_shape_demand = None;
+
+        // The market may now be exhausted, which ends the match by tender
+        if let Some(result) = Self::check_game_end(state) {
+            Self::finish_match(state, result, events);
+            return Ok(());
+        }
+
+        // Advance turn
+        Self::advance_turn(state);
+
+        Ok(())
+    }
+
+    /// Apply General Market effect (all other players draw 1)
+    pub fn apply_general_market(state: &mut MatchData, events: &mut Vec<MatchEvent>) {
+        let current_idx = state.current_player_index;
+        let next_version = state.version + 1;
+        let receivers: Vec<usize> = (0..state.players.len())
+            .filter(|&i| i != current_idx && Self::in_rotation(state, i))
+            .collect();
+        for i in receivers {
+            if let Some(card) = state.deck.pop() {
+                events.push(
+                    MatchEvent::new(next_version, Some(i), MatchEventKind::CardsDrawn)
+                        .with_cards(std::slice::from_ref(&card)),
+                );
+                let player = &mut state.players[i];
+                player.cards.push(card);
+                player.update_card_count();
+            }
+        }
+    }
+
+    /// Revert to a snapshot taken just before `seat`'s last move (casual takebacks)
+    ///
+    /// The version keeps counting forward so clients notice the change, and
+    /// the takeback that was just settled does not come back with the snapshot.
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn revert(state: &mut MatchData, snapshot: MatchData, seat: usize, events: &mut Vec<MatchEvent>) {
+        let version = state.version;
+        *state = snapshot;
+        state.version = version;
+        state.takeback_request = None;
+        state.takeback_votes.clear();
+        events.push(MatchEvent::new(version + 1, Some(seat), MatchEventKind::TakebackApplied));
+    }
+
+    /// Record a match result, tally the remaining hands and finish the match
+    pub fn finish_match(state: &mut MatchData, result: GameResult, events: &mut Vec<MatchEvent>) {
+        match result {
+            GameResult::Winner(idx) => {
+                state.winner_index = Some(idx);
+            }
+            GameResult::TeamWinner { team, .. } => {
+                state.winning_team = Some(team);
+            }
+            GameResult::Placements(order) => {
+                state.winner_index = order.first().copied();
+                state.placements = order;
+            }
+            GameResult::Draw(tied) => {
+                state.tied_players = tied;
+            }
+        }
+        state.final_tally = state
+            .players
+            .iter()
+            .map(|p| Self::hand_total(&p.cards))
+            .collect();
+        state.status = MatchStatus::Finished;
+
+        events.push(MatchEvent::new(
+            state.version + 1,
+            state.winner_index,
+            MatchEventKind::MatchFinished,
+        ));
     }
 
     /// Check if the game has ended
     pub fn check_game_end(state: &MatchData) -> Option<GameResult> {
+        if state.play_out {
+            return Self::check_play_out_end(state);
+        }
+
         // Check if any player has won (0 cards)
         for (i, player) in state.players.iter().enumerate() {
             if player.is_active && player.card_count == 0 {
-                return Some(GameResult::Winner(i));
+                // In a partnership, emptying either partner's hand wins for the team
+                return Some(match Self::team_of(state, i) {
+                    Some(team) => GameResult::TeamWinner {
+                        team,
+                        players: Self::team_members(state, team),
+                    },
+                    None => GameResult::Winner(i),
+                });
             }
         }
 
-        // Check if deck is empty and no valid moves (stalemate)
-        if state.deck.is_empty() && state.status == crate::state::MatchStatus::InProgress {
-            // Find player with fewest cards
-            let min_cards = state
-                .players
-                .iter()
-                .filter(|p| p.is_active)
-                .map(|p| p.card_count)
-                .min()
-                .unwrap_or(0);
+        // Market exhausted: "tender" by totalling what is left in each hand
+        if state.status == MatchStatus::InProgress && Self::is_market_exhausted(state) {
+            return Some(Self::tender(state));
+        }
+
+        None
+    }
+
+    /// Check if a play-out match has ended: one player left in the rotation,
+    /// or the market exhausted
+    ///
+    /// Seats still playing are placed by hand total (lowest first, ties in
+    /// seat order), followed by seats that left the match.
+    fn check_play_out_end(state: &MatchData) -> Option<GameResult> {
+        let remaining: Vec<usize> = (0..state.players.len())
+            .filter(|&seat| Self::in_rotation(state, seat))
+            .collect();
+        let exhausted = state.status == MatchStatus::InProgress && Self::is_market_exhausted(state);
+        if remaining.len() > 1 && !exhausted {
+            return None;
+        }
+
+        Some(Self::play_out_standings(state))
+    }
+
+    /// Final placements of a play-out match ended now
+    fn play_out_standings(state: &MatchData) -> GameResult {
+        let mut remaining: Vec<usize> = (0..state.players.len())
+            .filter(|&seat| Self::in_rotation(state, seat))
+            .collect();
+        remaining.sort_by_key(|&seat| Self::hand_total(&state.players[seat].cards));
+        let mut order = state.placements.clone();
+        order.extend(remaining);
+        order.extend((0..state.players.len()).filter(|seat| {
+            !state.players[*seat].is_active && !state.placements.contains(seat)
+        }));
+        GameResult::Placements(order)
+    }
+
+    /// Settle a match early by hand totals, as if the market had run out
+    pub fn settle_by_hand_totals(state: &MatchData) -> GameResult {
+        if state.play_out {
+            Self::play_out_standings(state)
+        } else {
+            Self::tender(state)
+        }
+    }
+
+    /// Whether the market can no longer supply a card, even by reshuffling
+    pub fn is_market_exhausted(state: &MatchData) -> bool {
+        state.deck.is_empty() && state.discard_pile.len() <= 1
+    }
+
+    /// Settle a match by hand totals: lowest total wins, equal lowest totals draw
+    ///
+    /// Partnerships are settled by the combined total of each team.
+    pub fn tender(state: &MatchData) -> GameResult {
+        if state.team_mode {
+            return Self::team_tender(state);
+        }
+
+        let totals: Vec<(usize, u32)> = state
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_active)
+            .map(|(i, p)| (i, Self::hand_total(&p.cards)))
+            .collect();
+        let lowest = totals.iter().map(|&(_, total)| total).min().unwrap_or(0);
+        let leaders: Vec<usize> = totals
+            .iter()
+            .filter(|&&(_, total)| total == lowest)
+            .map(|&(i, _)| i)
+            .collect();
+
+        match leaders.as_slice() {
+            [single] => GameResult::Winner(*single),
+            _ => GameResult::Draw(leaders),
+        }
+    }
+
+    /// Settle a partnership match by combined team totals
+    fn team_tender(state: &MatchData) -> GameResult {
+        let team_total = |team: u8| -> u32 {
+            Self::team_members(state, team)
+                .into_iter()
+                .filter(|&seat| state.players[seat].is_active)
+                .map(|seat| Self::hand_total(&state.players[seat].cards))
+                .sum()
+        };
+        let (first, second) = (team_total(0), team_total(1));
 
-            if let Some((idx, _)) = state
-                .players
-                .iter()
-                .enumerate()
-                .find(|(_, p)| p.is_active && p.card_count == min_cards)
-            {
-                return Some(GameResult::Winner(idx));
+        let team = match first.cmp(&second) {
+            std::cmp::Ordering::Less => 0,
+            std::cmp::Ordering::Greater => 1,
+            std::cmp::Ordering::Equal => {
+                let active = state
+                    .players
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.is_active)
+                    .map(|(i, _)| i)
+                    .collect();
+                return GameResult::Draw(active);
             }
+        };
+        GameResult::TeamWinner {
+            team,
+            players: Self::team_members(state, team),
         }
+    }
 
-        None
+    /// Point value of a card when hands are totalled
+    ///
+    /// Numbered cards count their face value, Whot counts 20, and Star cards
+    /// count double as in traditional Whot scoring.
+    pub fn card_points(card: &Card) -> u32 {
+        let face = match card.value {
+            CardValue::One | CardValue::HoldOn => 1,
+            CardValue::Two | CardValue::PickTwo => 2,
+            CardValue::Three => 3,
+            CardValue::Four => 4,
+            CardValue::Five | CardValue::PickThree => 5,
+            CardValue::Six => 6,
+            CardValue::Seven => 7,
+            CardValue::Eight | CardValue::Suspension => 8,
+            CardValue::Nine => 9,
+            CardValue::Ten => 10,
+            CardValue::Eleven => 11,
+            CardValue::Twelve => 12,
+            CardValue::Thirteen => 13,
+            CardValue::Fourteen | CardValue::GeneralMarket => 14,
+            CardValue::Whot => return 20,
+        };
+
+        if card.suit == CardSuit::Star {
+            face * 2
+        } else {
+            face
+        }
+    }
+
+    /// Total point value of a hand
+    pub fn hand_total(cards: &[Card]) -> u32 {
+        cards.iter().map(Self::card_points).sum()
+    }
+
+    /// Split a betting pool according to a match result
+    ///
+    /// The winner takes the whole pool. A winning team, or the players
+    /// sharing a draw, split it evenly. Play-out placements split it by the
+    /// `prize_split` weights (winner takes all if none are set). Any
+    /// remainder goes one unit each to the paid players in order.
+    #[allow(dead_code)] // Used in service.rs
+    pub fn payout_shares(result: &GameResult, pool: u64, prize_split: &[u32]) -> Vec<(usize, u64)> {
+        let paid: Vec<(usize, u64)> = match result {
+            GameResult::Winner(idx) => vec![(*idx, 1)],
+            GameResult::TeamWinner { players, .. } => players.iter().map(|&idx| (idx, 1)).collect(),
+            GameResult::Draw(tied) => tied.iter().map(|&idx| (idx, 1)).collect(),
+            GameResult::Placements(order) => {
+                let weighted: Vec<(usize, u64)> = order
+                    .iter()
+                    .zip(prize_split)
+                    .filter(|(_, &weight)| weight > 0)
+                    .map(|(&idx, &weight)| (idx, u64::from(weight)))
+                    .collect();
+                if weighted.is_empty() {
+                    order.first().map(|&idx| vec![(idx, 1)]).unwrap_or_default()
+                } else {
+                    weighted
+                }
+            }
+        };
+        if paid.is_empty() {
+            return Vec::new();
+        }
+
+        let total_weight: u64 = paid.iter().map(|&(_, weight)| weight).sum();
+        let mut shares: Vec<(usize, u64)> = paid
+            .iter()
+            .map(|&(idx, weight)| {
+                (idx, (u128::from(pool) * u128::from(weight) / u128::from(total_weight)) as u64)
+            })
+            .collect();
+        let remainder = pool - shares.iter().map(|&(_, amount)| amount).sum::<u64>();
+        for n in 0..remainder as usize {
+            let count = shares.len();
+            shares[n % count].1 += 1;
+        }
+        shares
+    }
+
+    /// Result of a finished match, rebuilt from the stored match data
+    pub fn final_result(state: &MatchData) -> Option<GameResult> {
+        if state.status != MatchStatus::Finished {
+            return None;
+        }
+        if let Some(team) = state.winning_team {
+            return Some(GameResult::TeamWinner {
+                team,
+                players: Self::team_members(state, team),
+            });
+        }
+        if state.play_out && !state.placements.is_empty() {
+            return Some(GameResult::Placements(state.placements.clone()));
+        }
+        match state.winner_index {
+            Some(idx) => Some(GameResult::Winner(idx)),
+            None => Some(GameResult::Draw(state.tied_players.clone())),
+        }
+    }
+
+    /// Seats in finishing order, 1st first
+    ///
+    /// Play-out placements are used as recorded; otherwise the winners lead
+    /// and the other seats follow by final hand total, lowest first.
+    #[allow(dead_code)] // Used in contract.rs and service.rs
+    pub fn finishing_order(state: &MatchData) -> Vec<usize> {
+        let winners = match Self::final_result(state) {
+            Some(GameResult::Placements(order)) => return order,
+            Some(GameResult::Winner(idx)) => vec![idx],
+            Some(GameResult::TeamWinner { players, .. }) => players,
+            Some(GameResult::Draw(tied)) => tied,
+            None => return Vec::new(),
+        };
+
+        let mut rest: Vec<usize> = (0..state.players.len())
+            .filter(|seat| !winners.contains(seat))
+            .collect();
+        rest.sort_by_key(|&seat| state.final_tally.get(seat).copied().unwrap_or(u32::MAX));
+        winners.into_iter().chain(rest).collect()
+    }
+
+    /// Result of a finished match by owner, as reported to other applications
+    #[allow(dead_code)] // Used in contract.rs and service.rs
+    pub fn match_result(state: &MatchData) -> Option<MatchResult> {
+        let result = Self::final_result(state)?;
+        let owners = |seats: &[usize]| -> Vec<AccountOwner> {
+            seats.iter().filter_map(|&seat| state.players.get(seat)).map(|p| p.owner).collect()
+        };
+        let winners = match &result {
+            GameResult::Winner(idx) => vec![*idx],
+            GameResult::TeamWinner { players, .. } => players.clone(),
+            GameResult::Draw(tied) => tied.clone(),
+            GameResult::Placements(order) => order.iter().take(1).copied().collect(),
+        };
+
+        Some(MatchResult {
+            winners: owners(&winners),
+            placements: owners(&Self::finishing_order(state)),
+            drawn: matches!(result, GameResult::Draw(_)),
+            winning_team: state.winning_team,
+            final_tally: state.final_tally.clone(),
+            version: state.version,
+        })
+    }
+
+    /// Audit a match state for card conservation and internal consistency
+    ///
+    /// Once cards are dealt, the deck, discard pile and every hand together
+    /// must hold exactly the cards produced by `create_deck`, each once.
+    /// Returns a description of every violation found; empty means sound.
+    pub fn audit(state: &MatchData) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        // Card counts must mirror the hands they describe
+        for (i, player) in state.players.iter().enumerate() {
+            if player.card_count != player.cards.len() {
+                violations.push(format!(
+                    "player {} card_count is {} but holds {} cards",
+                    i,
+                    player.card_count,
+                    player.cards.len()
+                ));
+            }
+        }
+
+        // Card conservation
+        let in_play = state
+            .deck
+            .iter()
+            .chain(state.discard_pile.iter())
+            .chain(state.players.iter().flat_map(|p| p.cards.iter()));
+        // (a match cancelled once everyone left still holds its cards)
+        let dealt = match state.status {
+            MatchStatus::Waiting => false,
+            MatchStatus::Cancelled => !state.discard_pile.is_empty(),
+            _ => true,
+        };
+        if dealt {
+            let reference = Self::create_deck();
+            let mut seen = vec![0usize; reference.len()];
+            for card in in_play {
+                match reference.get(card.id as usize) {
+                    Some(original)
+                        if original.suit == card.suit && original.value == card.value =>
+                    {
+                        seen[card.id as usize] += 1;
+                    }
+                    Some(_) => violations.push(format!("card {} has been altered", card.id)),
+                    None => violations.push(format!("unknown card {}", card.id)),
+                }
+            }
+            for (id, &copies) in seen.iter().enumerate() {
+                match copies {
+                    1 => {}
+                    0 => violations.push(format!("card {} has vanished", id)),
+                    n => violations.push(format!("card {} appears {} times", id, n)),
+                }
+            }
+        } else if in_play.count() > 0 {
+            violations.push("cards are in play before the match started".to_string());
+        }
+
+        // Index bounds
+        if !state.players.is_empty() && state.current_player_index >= state.players.len() {
+            violations.push(format!(
+                "current_player_index {} is out of bounds",
+                state.current_player_index
+            ));
+        }
+        if let Some(winner) = state.winner_index {
+            if winner >= state.players.len() {
+                violations.push(format!("winner_index {} is out of bounds", winner));
+            }
+        }
+        if let Some(team) = state.winning_team {
+            if !state.team_mode || team > 1 {
+                violations.push(format!("winning_team {} is not a team of this match", team));
+            }
+        }
+        let mut placed = vec![false; state.players.len()];
+        for &seat in &state.placements {
+            match placed.get_mut(seat) {
+                Some(seen) if !*seen => *seen = true,
+                Some(_) => violations.push(format!("seat {} is placed twice", seat)),
+                None => violations.push(format!("placed seat {} is out of bounds", seat)),
+            }
+        }
+        if state.pending_penalty > 0 && state.penalty_source.is_none() {
+            violations.push("pending penalty has no source".to_string());
+        }
+        if state.takeback_request.is_some_and(|seat| seat >= state.players.len())
+            || state.takeback_votes.iter().any(|&seat| seat >= state.players.len())
+        {
+            violations.push("takeback vote from a seat out of bounds".to_string());
+        }
+        if state.pause_votes.iter().any(|&seat| seat >= state.players.len()) {
+            violations.push("pause vote from a seat out of bounds".to_string());
+        }
+        if state.paused_at.is_some() != (state.status == MatchStatus::Paused) {
+            violations.push("pause timestamp does not match the status".to_string());
+        }
+
+        // Status coherence
+        match state.status {
+            MatchStatus::Waiting | MatchStatus::Cancelled => {
+                if state.winner_index.is_some() {
+                    violations.push("unstarted match has a winner".to_string());
+                }
+                if state.pending_penalty > 0 || state.turn_phase != TurnPhase::Play {
+                    violations.push("unstarted match has turn state".to_string());
+                }
+            }
+            MatchStatus::InProgress | MatchStatus::Paused => {
+                if state.players.is_empty() {
+                    violations.push("match in progress has no players".to_string());
+                }
+                if state.discard_pile.is_empty() {
+                    violations.push("match in progress has an empty discard pile".to_string());
+                }
+                if state.winner_index.is_some() || state.winning_team.is_some() {
+                    violations.push("match in progress has a winner".to_string());
+                }
+                if state.turn_phase == TurnPhase::ChooseSuit
+                    && state.discard_pile.last().map(|card| card.value) != Some(CardValue::Whot)
+                {
+                    violations.push("suit choice pending without a Whot on top".to_string());
+                }
+                for &seat in &state.placements {
+                    if state.players.get(seat).is_some_and(|p| !p.cards.is_empty()) {
+                        violations.push(format!("placed seat {} still holds cards", seat));
+                    }
+                }
+            }
+            MatchStatus::Finished => {
+                if state.winner_index.is_none()
+                    && state.winning_team.is_none()
+                    && state.tied_players.is_empty()
+                {
+                    violations.push("finished match has neither a winner nor a draw".to_string());
+                }
+                if state.play_out && state.placements.len() != state.players.len() {
+                    violations.push("finished play-out match has not placed every seat".to_string());
+                }
+            }
+        }
+
+        violations
     }
 }
 
@@ -231,11 +1029,355 @@ pub enum SpecialEffect {
 }
 
 /// Game result enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameResult {
     /// Player at index won
     Winner(usize),
-    /// Game ended in draw (future feature)
-    #[allow(dead_code)]
-    Draw,
+    /// Partnership won: team number and its seats
+    TeamWinner { team: u8, players: Vec<usize> },
+    /// Play-out finished: every seat in finishing order, 1st first
+    Placements(Vec<usize>),
+    /// Game ended in a draw between the players at these indices
+    Draw(Vec<usize>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Player;
+    use linera_sdk::linera_base_types::{AccountOwner, ApplicationId, ChainId};
+
+    /// A freshly dealt match between `players` seats
+    fn dealt(players: u8) -> MatchData {
+        let mut deck = GameEngine::create_deck();
+        GameEngine::shuffle_with_seed(&mut deck, b"seed");
+        let hands = GameEngine::deal_initial_hands(&mut deck, usize::from(players));
+        let players = hands
+            .into_iter()
+            .enumerate()
+            .map(|(seat, cards)| Player {
+                card_count: cards.len(),
+                cards,
+                ..Player::new(AccountOwner::Reserved(seat as u8), format!("Player {}", seat))
+            })
+            .collect();
+        let discard_pile = deck.pop().into_iter().collect();
+        MatchData {
+            status: MatchStatus::InProgress,
+            players,
+            deck,
+            discard_pile,
+            ..MatchData::default()
+        }
+    }
+
+    fn card(suit: CardSuit, value: CardValue) -> Card {
+        Card { id: 0, suit, value }
+    }
+
+    /// A match in progress where each seat holds the given hand
+    fn holding(hands: Vec<Vec<Card>>) -> MatchData {
+        let players = hands
+            .into_iter()
+            .enumerate()
+            .map(|(seat, cards)| Player {
+                card_count: cards.len(),
+                cards,
+                ..Player::new(AccountOwner::Reserved(seat as u8), format!("Player {}", seat))
+            })
+            .collect();
+        MatchData {
+            status: MatchStatus::InProgress,
+            players,
+            ..MatchData::default()
+        }
+    }
+
+    #[test]
+    fn tables_on_one_chain_and_rematches_are_dealt_differently() {
+        let chain: ChainId = "0".repeat(64).parse().unwrap();
+        let table = |hash: &str| ApplicationId::new(hash.repeat(64).parse().unwrap());
+        let deal = |seed: Vec<u8>| {
+            let mut deck = GameEngine::create_deck();
+            GameEngine::shuffle_with_seed(&mut deck, &seed);
+            deck.iter().map(|card| card.id).collect::<Vec<_>>()
+        };
+
+        let first = GameEngine::match_seed(chain, table("1"), 0);
+        let second = GameEngine::match_seed(chain, table("2"), 0);
+        let rematch = GameEngine::match_seed(chain, table("1"), 1);
+        assert_ne!(first, second);
+        assert_ne!(first, rematch);
+        assert_ne!(deal(first.clone()), deal(second));
+        assert_ne!(deal(first), deal(rematch));
+    }
+
+    #[test]
+    fn card_points_count_face_value_with_stars_doubled() {
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Circle, CardValue::Seven)), 7);
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Cross, CardValue::PickTwo)), 2);
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Square, CardValue::GeneralMarket)), 14);
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Star, CardValue::Seven)), 14);
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Circle, CardValue::Whot)), 20);
+        assert_eq!(GameEngine::card_points(&card(CardSuit::Star, CardValue::Whot)), 20);
+    }
+
+    #[test]
+    fn tender_awards_the_lowest_hand() {
+        let state = holding(vec![
+            vec![card(CardSuit::Circle, CardValue::Ten)],
+            vec![card(CardSuit::Cross, CardValue::Three), card(CardSuit::Square, CardValue::Four)],
+            vec![card(CardSuit::Star, CardValue::Five)],
+        ]);
+        assert_eq!(GameEngine::tender(&state), GameResult::Winner(1));
+    }
+
+    #[test]
+    fn tender_draws_between_equal_lowest_hands_and_skips_leavers() {
+        let mut state = holding(vec![
+            vec![card(CardSuit::Circle, CardValue::Six)],
+            vec![card(CardSuit::Cross, CardValue::Two)],
+            vec![card(CardSuit::Star, CardValue::Three)],
+            vec![card(CardSuit::Triangle, CardValue::Six)],
+        ]);
+        state.players[1].is_active = false;
+        assert_eq!(GameEngine::tender(&state), GameResult::Draw(vec![0, 2, 3]));
+    }
+
+    #[test]
+    fn team_tender_compares_combined_totals() {
+        let mut state = holding(vec![
+            vec![card(CardSuit::Circle, CardValue::One)],
+            vec![card(CardSuit::Cross, CardValue::Two)],
+            vec![card(CardSuit::Circle, CardValue::Twelve)],
+            vec![card(CardSuit::Cross, CardValue::Eight)],
+        ]);
+        state.team_mode = true;
+        assert_eq!(
+            GameEngine::tender(&state),
+            GameResult::TeamWinner {
+                team: 1,
+                players: vec![1, 3],
+            }
+        );
+
+        state.players[2].cards = vec![card(CardSuit::Circle, CardValue::Nine)];
+        assert_eq!(GameEngine::tender(&state), GameResult::Draw(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn payout_goes_to_the_winner() {
+        assert_eq!(GameEngine::payout_shares(&GameResult::Winner(2), 100, &[]), vec![(2, 100)]);
+    }
+
+    #[test]
+    fn payout_splits_draws_evenly_with_the_remainder_in_order() {
+        let shares = GameEngine::payout_shares(&GameResult::Draw(vec![0, 2, 3]), 100, &[]);
+        assert_eq!(shares, vec![(0, 34), (2, 33), (3, 33)]);
+    }
+
+    #[test]
+    fn payout_follows_the_prize_split_for_placements() {
+        let placements = GameResult::Placements(vec![3, 0, 1, 2]);
+        let shares = GameEngine::payout_shares(&placements, 1000, &[60, 30, 10]);
+        assert_eq!(shares, vec![(3, 600), (0, 300), (1, 100)]);
+
+        // Without a split the winner takes all
+        assert_eq!(GameEngine::payout_shares(&placements, 1000, &[]), vec![(3, 1000)]);
+    }
+
+    #[test]
+    fn payout_skips_unweighted_places_and_empty_results() {
+        let placements = GameResult::Placements(vec![1, 0]);
+        assert_eq!(GameEngine::payout_shares(&placements, 10, &[0, 1]), vec![(0, 10)]);
+        assert!(GameEngine::payout_shares(&GameResult::Draw(Vec::new()), 10, &[]).is_empty());
+    }
+
+    #[test]
+    fn audit_accepts_a_fresh_deal() {
+        let state = dealt(3);
+        assert!(GameEngine::audit(&state).is_empty());
+    }
+
+    #[test]
+    fn audit_reports_lost_and_duplicated_cards() {
+        let mut state = dealt(2);
+        let lost = state.deck.pop().unwrap();
+        let copied = state.deck[0].clone();
+        state.deck.push(copied.clone());
+
+        let violations = GameEngine::audit(&state);
+        assert!(violations.contains(&format!("card {} has vanished", lost.id)));
+        assert!(violations.contains(&format!("card {} appears 2 times", copied.id)));
+    }
+
+    #[test]
+    fn audit_reports_stale_card_counts_and_altered_cards() {
+        let mut state = dealt(2);
+        state.players[0].card_count += 1;
+        state.players[1].cards[0].value = CardValue::Whot;
+        let altered = state.players[1].cards[0].id;
+
+        let violations = GameEngine::audit(&state);
+        assert!(violations.iter().any(|v| v.starts_with("player 0 card_count")));
+        assert!(violations.contains(&format!("card {} has been altered", altered)));
+    }
+
+    #[test]
+    fn audit_reports_incoherent_status() {
+        let waiting = MatchData {
+            players: vec![Player::new(AccountOwner::Reserved(0), "Solo".to_string())],
+            winner_index: Some(0),
+            ..MatchData::default()
+        };
+        assert!(GameEngine::audit(&waiting).contains(&"unstarted match has a winner".to_string()));
+
+        let mut won = dealt(2);
+        won.winner_index = Some(1);
+        assert!(GameEngine::audit(&won).contains(&"match in progress has a winner".to_string()));
+
+        let mut finished = dealt(2);
+        finished.status = MatchStatus::Finished;
+        let violations = GameEngine::audit(&finished);
+        assert!(violations.contains(&"finished match has neither a winner nor a draw".to_string()));
+    }
+
+    /// Four seats in a partnership match with a penalty laid by seat 0
+    fn partnership_penalty(partner_can_defend: bool) -> MatchData {
+        MatchData {
+            status: MatchStatus::InProgress,
+            team_mode: true,
+            partner_can_defend,
+            pending_penalty: 2,
+            penalty_source: Some(0),
+            ..MatchData::default()
+        }
+    }
+
+    #[test]
+    fn partner_may_not_defend_when_the_variant_forbids_it() {
+        let mut state = partnership_penalty(false);
+        assert!(GameEngine::may_defend(&state, 1));
+        assert!(GameEngine::may_defend(&state, 3));
+        assert!(!GameEngine::may_defend(&state, 2));
+
+        // Seat 1 has left, so the penalty reached seat 0's partner
+        state.current_player_index = 2;
+        let result = GameEngine::play_card(&mut state, 0, None, &mut Vec::new());
+        assert!(matches!(result, Err(LinotError::PartnerPenalty)));
+    }
+
+    #[test]
+    fn partner_may_defend_by_default_and_outside_team_mode() {
+        let state = partnership_penalty(true);
+        assert!(GameEngine::may_defend(&state, 2));
+
+        let solo = MatchData {
+            team_mode: false,
+            ..partnership_penalty(false)
+        };
+        assert!(GameEngine::may_defend(&solo, 2));
+    }
+
+    #[test]
+    fn going_out_on_a_whot_in_play_out_passes_the_turn() {
+        let whot = Card { id: 1, suit: CardSuit::Star, value: CardValue::Whot };
+        let mut state = holding(vec![
+            vec![whot],
+            vec![card(CardSuit::Cross, CardValue::Two), card(CardSuit::Star, CardValue::Five)],
+            vec![card(CardSuit::Square, CardValue::Ten), card(CardSuit::Cross, CardValue::Four)],
+        ]);
+        state.play_out = true;
+        state.discard_pile = vec![card(CardSuit::Circle, CardValue::Three)];
+        state.deck = vec![card(CardSuit::Triangle, CardValue::Seven)];
+
+        GameEngine::play_card(&mut state, 1, None, &mut Vec::new()).unwrap();
+
+        assert_eq!(state.status, MatchStatus::InProgress);
+        assert_eq!(state.placements, vec![0]);
+        assert_eq!(state.turn_phase, TurnPhase::Play);
+        assert_eq!(state.active_shape_demand, None);
+        assert_eq!(state.current_player_index, 1);
+    }
+
+    #[test]
+    fn going_out_on_a_whot_in_play_out_lifts_the_standing_demand() {
+        let whot = Card { id: 1, suit: CardSuit::Star, value: CardValue::Whot };
+        let mut state = holding(vec![
+            vec![whot],
+            vec![card(CardSuit::Cross, CardValue::Two), card(CardSuit::Star, CardValue::Five)],
+            vec![card(CardSuit::Square, CardValue::Ten), card(CardSuit::Cross, CardValue::Four)],
+        ]);
+        state.play_out = true;
+        state.discard_pile = vec![Card { id: 2, suit: CardSuit::Star, value: CardValue::Whot }];
+        state.active_shape_demand = Some(CardSuit::Circle);
+        state.deck = vec![card(CardSuit::Triangle, CardValue::Seven)];
+
+        GameEngine::play_card(&mut state, 1, None, &mut Vec::new()).unwrap();
+
+        assert_eq!(state.placements, vec![0]);
+        assert_eq!(state.turn_phase, TurnPhase::Play);
+        assert_eq!(state.active_shape_demand, None);
+        assert_eq!(state.current_player_index, 1);
+    }
+
+    #[test]
+    fn winning_on_general_market_deals_nothing_after_the_tally() {
+        let market = Card { id: 1, suit: CardSuit::Circle, value: CardValue::GeneralMarket };
+        let mut state = holding(vec![
+            vec![market],
+            vec![card(CardSuit::Cross, CardValue::Four), card(CardSuit::Star, CardValue::Six)],
+        ]);
+        state.discard_pile = vec![card(CardSuit::Circle, CardValue::Three)];
+        state.deck = vec![card(CardSuit::Triangle, CardValue::Seven)];
+
+        GameEngine::play_card(&mut state, 1, None, &mut Vec::new()).unwrap();
+
+        assert_eq!(state.status, MatchStatus::Finished);
+        assert_eq!(state.winner_index, Some(0));
+        assert_eq!(state.players[1].card_count, 2);
+        assert_eq!(state.final_tally, vec![0, GameEngine::hand_total(&state.players[1].cards)]);
+        assert_eq!(state.deck.len(), 1);
+        assert_eq!(state.current_player_index, 0);
+    }
+
+    #[test]
+    fn winning_on_pick_two_leaves_no_penalty() {
+        let pick_two = Card { id: 1, suit: CardSuit::Circle, value: CardValue::PickTwo };
+        let mut state = holding(vec![vec![pick_two], vec![card(CardSuit::Cross, CardValue::Four)]]);
+        state.discard_pile = vec![card(CardSuit::Circle, CardValue::Three)];
+
+        GameEngine::play_card(&mut state, 1, None, &mut Vec::new()).unwrap();
+
+        assert_eq!(state.status, MatchStatus::Finished);
+        assert_eq!(state.pending_penalty, 0);
+        assert_eq!(state.current_player_index, 0);
+    }
+
+    #[test]
+    fn revert_restores_the_snapshot_without_its_takeback_request() {
+        let snapshot = MatchData {
+            status: MatchStatus::InProgress,
+            current_player_index: 1,
+            takeback_request: Some(0),
+            takeback_votes: vec![1],
+            version: 3,
+            ..MatchData::default()
+        };
+        let mut state = MatchData {
+            status: MatchStatus::InProgress,
+            version: 7,
+            ..MatchData::default()
+        };
+        let mut events = Vec::new();
+
+        GameEngine::revert(&mut state, snapshot, 0, &mut events);
+
+        assert_eq!(state.current_player_index, 1);
+        assert_eq!(state.version, 7);
+        assert_eq!(state.takeback_request, None);
+        assert!(state.takeback_votes.is_empty());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, MatchEventKind::TakebackApplied);
+    }
 }
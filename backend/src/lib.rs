@@ -2,7 +2,7 @@ use async_graphql::{Request, Response};
 use linera_sdk::{
     abi::{ContractAbi, ServiceAbi},
     graphql::GraphQLMutationRoot,
-    linera_base_types::AccountOwner,
+    linera_base_types::{AccountOwner, BcsHashable, CryptoHash},
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -25,6 +25,111 @@ pub enum LinotError {
     
     #[error("Only host can start match")]
     OnlyHostCanStart,
+
+    #[error("Only host can add bots")]
+    OnlyHostCanAddBots,
+
+    #[error("Bots cannot sit at a ranked table")]
+    BotsNotAllowed,
+
+    #[error("Only host can set up a new match")]
+    OnlyHostCanCreateMatch,
+
+    #[error("Table is in use: a match is seated or in progress")]
+    TableInUse,
+
+    #[error("This table is private: you are not on the invite list")]
+    NotInvited,
+
+    #[error("Invalid invite code")]
+    InvalidInviteCode,
+
+    #[error("The remaining seats are reserved")]
+    SeatReserved,
+
+    #[error("Invalid seat reservation for seat {0}")]
+    InvalidReservation(u8),
+
+    #[error("{0} player(s) not ready")]
+    PlayersNotReady(usize),
+
+    #[error("Lobby expired before the match started")]
+    LobbyExpired,
+
+    #[error("Lobby has not expired yet")]
+    LobbyNotExpired,
+
+    #[error("Player is already away")]
+    AlreadyAway,
+
+    #[error("Player is not away")]
+    NotAway,
+
+    #[error("Seat {0} does not hold the turn")]
+    NotTurnHolder(usize),
+
+    #[error("The turn has not been idle long enough to mark the player away")]
+    TurnNotIdle,
+
+    #[error("The away player's grace period has not ended")]
+    GracePeriodRunning,
+
+    #[error("Match is paused")]
+    MatchPaused,
+
+    #[error("Match is not paused")]
+    MatchNotPaused,
+
+    #[error("No pause has been requested")]
+    NoPauseRequested,
+
+    #[error("You have already agreed")]
+    AlreadyAgreed,
+
+    #[error("Takebacks are disabled in ranked matches")]
+    TakebacksDisabled,
+
+    #[error("You have no move to take back")]
+    NoTakeback,
+
+    #[error("No takeback has been requested")]
+    NoTakebackRequested,
+
+    #[error("Only seated players may chat at this table")]
+    ChatNotAllowed,
+
+    #[error("A chat message needs either text or an emote")]
+    InvalidChatMessage,
+
+    #[error("Chat message too long (max {0} characters)")]
+    ChatTooLong(usize),
+
+    #[error("Sending chat messages too fast")]
+    ChatRateLimited,
+
+    #[error("Invalid nickname: {0}")]
+    InvalidNickname(#[from] NicknameError),
+
+    #[error("Nickname is already in use")]
+    NicknameTaken,
+
+    #[error("A nickname is required (or register a profile first)")]
+    NicknameRequired,
+
+    #[error("Invalid session key (it must be a fresh account, not a seated player or another seat's key)")]
+    InvalidSessionKey,
+
+    #[error("Session keys may last 1 to {0} seconds")]
+    InvalidSessionLifetime(u64),
+
+    #[error("Session key expired")]
+    SessionKeyExpired,
+
+    #[error("Session key is not allowed to do that")]
+    SessionNotPermitted,
+
+    #[error("No session key authorized for this seat")]
+    NoSessionKey,
     
     #[error("Need at least {0} players to start")]
     NotEnoughPlayers(usize),
@@ -32,8 +137,20 @@ pub enum LinotError {
     #[error("Not your turn")]
     NotYourTurn,
     
-    #[error("Invalid card index: {0}")]
-    InvalidCardIndex(usize),
+    #[error("Caller is not seated in this match")]
+    NotAPlayer,
+
+    #[error("Team mode needs exactly {0} players")]
+    TeamModeNeedsPlayers(usize),
+
+    #[error("Play-out mode is not available in team mode")]
+    PlayOutWithTeams,
+
+    #[error("Cannot defend a penalty laid by your partner")]
+    PartnerPenalty,
+
+    #[error("Card {0} is not in your hand")]
+    CardNotInHand(u32),
     
     #[error("Invalid card play: card doesn't match suit, value, or special requirements")]
     InvalidCardPlay,
@@ -52,6 +169,84 @@ pub enum LinotError {
     
     #[error("Caller authentication required")]
     CallerRequired,
+
+    #[error("Waiting for the Whot player to choose a suit")]
+    SuitChoicePending,
+
+    #[error("No suit choice is pending")]
+    NoSuitChoicePending,
+
+    #[error("A suit can only be chosen when playing a Whot card")]
+    UnexpectedSuitChoice,
+
+    #[error("Stale state: expected version {expected}, match is at version {current}")]
+    StaleVersion { expected: u64, current: u64 },
+}
+
+impl LinotError {
+    /// Stable name of the error, for clients that branch on the reason
+    /// rather than parse the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            LinotError::MatchAlreadyStarted => "MatchAlreadyStarted",
+            LinotError::MatchNotStarted => "MatchNotStarted",
+            LinotError::MatchFull(_) => "MatchFull",
+            LinotError::PlayerAlreadyJoined => "PlayerAlreadyJoined",
+            LinotError::OnlyHostCanStart => "OnlyHostCanStart",
+            LinotError::OnlyHostCanAddBots => "OnlyHostCanAddBots",
+            LinotError::BotsNotAllowed => "BotsNotAllowed",
+            LinotError::OnlyHostCanCreateMatch => "OnlyHostCanCreateMatch",
+            LinotError::TableInUse => "TableInUse",
+            LinotError::NotInvited => "NotInvited",
+            LinotError::InvalidInviteCode => "InvalidInviteCode",
+            LinotError::SeatReserved => "SeatReserved",
+            LinotError::InvalidReservation(_) => "InvalidReservation",
+            LinotError::PlayersNotReady(_) => "PlayersNotReady",
+            LinotError::LobbyExpired => "LobbyExpired",
+            LinotError::LobbyNotExpired => "LobbyNotExpired",
+            LinotError::AlreadyAway => "AlreadyAway",
+            LinotError::NotAway => "NotAway",
+            LinotError::NotTurnHolder(_) => "NotTurnHolder",
+            LinotError::TurnNotIdle => "TurnNotIdle",
+            LinotError::GracePeriodRunning => "GracePeriodRunning",
+            LinotError::MatchPaused => "MatchPaused",
+            LinotError::MatchNotPaused => "MatchNotPaused",
+            LinotError::NoPauseRequested => "NoPauseRequested",
+            LinotError::AlreadyAgreed => "AlreadyAgreed",
+            LinotError::TakebacksDisabled => "TakebacksDisabled",
+            LinotError::NoTakeback => "NoTakeback",
+            LinotError::NoTakebackRequested => "NoTakebackRequested",
+            LinotError::ChatNotAllowed => "ChatNotAllowed",
+            LinotError::InvalidChatMessage => "InvalidChatMessage",
+            LinotError::ChatTooLong(_) => "ChatTooLong",
+            LinotError::ChatRateLimited => "ChatRateLimited",
+            LinotError::InvalidNickname(_) => "InvalidNickname",
+            LinotError::NicknameTaken => "NicknameTaken",
+            LinotError::NicknameRequired => "NicknameRequired",
+            LinotError::InvalidSessionKey => "InvalidSessionKey",
+            LinotError::InvalidSessionLifetime(_) => "InvalidSessionLifetime",
+            LinotError::SessionKeyExpired => "SessionKeyExpired",
+            LinotError::SessionNotPermitted => "SessionNotPermitted",
+            LinotError::NoSessionKey => "NoSessionKey",
+            LinotError::NotEnoughPlayers(_) => "NotEnoughPlayers",
+            LinotError::NotYourTurn => "NotYourTurn",
+            LinotError::NotAPlayer => "NotAPlayer",
+            LinotError::TeamModeNeedsPlayers(_) => "TeamModeNeedsPlayers",
+            LinotError::PlayOutWithTeams => "PlayOutWithTeams",
+            LinotError::PartnerPenalty => "PartnerPenalty",
+            LinotError::CardNotInHand(_) => "CardNotInHand",
+            LinotError::InvalidCardPlay => "InvalidCardPlay",
+            LinotError::InvalidPlayerIndex(_) => "InvalidPlayerIndex",
+            LinotError::MatchNotInProgress => "MatchNotInProgress",
+            LinotError::NoCardInDiscardPile => "NoCardInDiscardPile",
+            LinotError::BettingNotImplemented => "BettingNotImplemented",
+            LinotError::CallerRequired => "CallerRequired",
+            LinotError::SuitChoicePending => "SuitChoicePending",
+            LinotError::NoSuitChoicePending => "NoSuitChoicePending",
+            LinotError::UnexpectedSuitChoice => "UnexpectedSuitChoice",
+            LinotError::StaleVersion { .. } => "StaleVersion",
+        }
+    }
 }
 
 // ============ ABI Definition ============
@@ -60,7 +255,7 @@ pub struct LinotAbi;
 
 impl ContractAbi for LinotAbi {
     type Operation = Operation;
-    type Response = ();
+    type Response = LinotResponse;
 }
 
 impl ServiceAbi for LinotAbi {
@@ -72,6 +267,8 @@ impl ServiceAbi for LinotAbi {
 
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
 pub struct Card {
+    /// Stable identity of this physical card, assigned when the deck is created
+    pub id: u32,
     pub suit: CardSuit,
     pub value: CardValue,
 }
@@ -109,54 +306,606 @@ pub enum CardValue {
     Suspension,
 }
 
+/// Fixed set of emotes that can be sent in chat
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum Emote {
+    GoodGame,
+    WellPlayed,
+    Thanks,
+    Oops,
+    Hurry,
+    Laugh,
+    Wow,
+    Sorry,
+}
+
+/// Playing strength of a bot seat
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::Enum, PartialEq, Eq)]
+pub enum BotDifficulty {
+    /// Plays the first legal card it finds
+    Easy,
+    /// Sheds high-value cards first and keeps Whot for emergencies
+    Medium,
+    /// Also defends penalties, attacks players close to winning, and picks suits by hand strength
+    Hard,
+}
+
 // ============ Match Configuration ============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(input_name = "MatchConfigInput")]
 pub struct MatchConfig {
+    /// Maximum players allowed (2 for V1)
     pub max_players: u8,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Host account who created the match
     pub host: Option<AccountOwner>,
+    /// Whether this is a ranked/competitive match
     pub is_ranked: bool,
-    pub strict_mode: bool, // Enforce "must draw if no valid move"
+    /// Strict mode: must draw if no valid move
+    pub strict_mode: bool,
+    /// Partnership mode: four players, seats alternate between two teams
+    #[serde(default)]
+    pub team_mode: bool,
+    /// Whether a player may defend a Pick Two/Three laid by their partner
+    #[serde(default = "default_true")]
+    pub partner_can_defend: bool,
+    /// Play-out mode: players who go out leave the rotation and the match
+    /// continues until one player remains, ranking every seat
+    #[serde(default)]
+    pub play_out: bool,
+    /// Betting pool weights by place in play-out matches, e.g. `[60, 30, 10]`
+    /// (empty means the winner takes the pool)
+    #[serde(default)]
+    pub prize_split: Vec<u32>,
+    /// Private table: only these accounts (plus the host and reserved
+    /// players) may join; empty means anyone may, unless an invite code is set
+    #[serde(default)]
+    pub allowlist: Vec<AccountOwner>,
+    /// Private table: hash of the invite code (see `hash_invite_code`) that
+    /// players outside the allowlist must present with `JoinMatch`
+    #[serde(default)]
+    pub invite_code_hash: Option<CryptoHash>,
+    /// Seats held for specific players; nobody else may take them, and each
+    /// reserved player is moved to their seat when the match starts
+    #[serde(default)]
+    pub reserved_seats: Vec<SeatReservation>,
+    /// Start automatically once every seat is filled and every player is ready
+    #[serde(default)]
+    pub auto_start: bool,
+    /// Cancel the table if it is still waiting this many seconds after creation
+    #[serde(default)]
+    pub lobby_expiry_secs: Option<u64>,
+    /// How long an away player keeps their seat before anyone may forfeit it
+    #[serde(default = "default_grace_period_secs")]
+    pub grace_period_secs: u64,
+    /// How long the turn holder may idle before other players may mark them away
+    #[serde(default = "default_idle_turn_secs")]
+    pub idle_turn_secs: u64,
+    /// Pause play on an away player's turn instead of drawing for them
+    #[serde(default)]
+    pub pause_when_away: bool,
+    /// Let spectators (anyone not seated) post in the match chat
+    #[serde(default)]
+    pub spectator_chat: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_grace_period_secs() -> u64 {
+    120
+}
+
+fn default_idle_turn_secs() -> u64 {
+    60
+}
+
+impl MatchConfig {
+    /// Whether joining needs an invitation (allowlist or invite code)
+    pub fn is_private(&self) -> bool {
+        !self.allowlist.is_empty() || self.invite_code_hash.is_some()
+    }
+
+    /// Seat reserved for `owner`, if any
+    pub fn reserved_seat(&self, owner: AccountOwner) -> Option<u8> {
+        self.reserved_seats
+            .iter()
+            .find(|reservation| reservation.owner == owner)
+            .map(|reservation| reservation.seat)
+    }
+}
+
+/// A seat held for one player at a private table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, async_graphql::SimpleObject, async_graphql::InputObject)]
+#[graphql(input_name = "SeatReservationInput")]
+pub struct SeatReservation {
+    /// Seat index (0-based turn order)
+    pub seat: u8,
+    /// Player the seat is held for
+    pub owner: AccountOwner,
+}
+
+/// Invite code in the form it is hashed for `MatchConfig::invite_code_hash`
+#[derive(Serialize, Deserialize)]
+struct InviteCode(String);
+
+impl BcsHashable<'_> for InviteCode {}
+
+/// Hash an invite code for `MatchConfig::invite_code_hash`
+///
+/// Only the hash is stored on chain. The code itself is sent in the clear
+/// with `JoinMatch`, so it keeps strangers out but is not a secret from
+/// anyone reading the chain once a player has used it.
+pub fn hash_invite_code(code: &str) -> CryptoHash {
+    CryptoHash::new(&InviteCode(code.to_string()))
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            max_players: 2,
+            host: None,
+            is_ranked: false,
+            strict_mode: false,
+            team_mode: false,
+            partner_can_defend: true,
+            play_out: false,
+            prize_split: Vec::new(),
+            allowlist: Vec::new(),
+            invite_code_hash: None,
+            reserved_seats: Vec::new(),
+            auto_start: false,
+            lobby_expiry_secs: None,
+            grace_period_secs: default_grace_period_secs(),
+            idle_turn_secs: default_idle_turn_secs(),
+            pause_when_away: false,
+            spectator_chat: false,
+        }
+    }
+}
+
+// ============ Match Status ============
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
+pub enum MatchStatus {
+    /// Waiting for players to join
+    Waiting,
+    /// Match is in progress
+    InProgress,
+    /// Match is suspended by agreement; game actions and timers are frozen
+    Paused,
+    /// Match has finished
+    Finished,
+    /// Lobby expired before the match started, or everyone left the match
+    Cancelled,
 }
 
 // ============ Operations (GraphQL Mutations) ============
 
 #[derive(Debug, Deserialize, Serialize, GraphQLMutationRoot)]
 pub enum Operation {
-    /// Join this match instance
+    /// Join this match instance (private tables need an invitation); a
+    /// registered profile name is used instead of `nickname` when there is one.
+    /// `session` authorizes a session key for the new seat straight away.
     JoinMatch {
-        nickname: String,
+        nickname: Option<String>,
+        invite_code: Option<String>,
+        session: Option<SessionGrant>,
+        expected_version: Option<u64>,
     },
 
-    /// Start the match (host only)
-    StartMatch,
+    /// Fill an open seat with an engine-driven bot (host only)
+    AddBot {
+        difficulty: BotDifficulty,
+        expected_version: Option<u64>,
+    },
+
+    /// Mark yourself ready (or not) while the table is waiting
+    SetReady {
+        ready: bool,
+        expected_version: Option<u64>,
+    },
+
+    /// Cancel a table left waiting past its lobby expiry (anyone)
+    ExpireLobby {
+        expected_version: Option<u64>,
+    },
+
+    /// Start the match (any seated player, once everyone else is ready)
+    StartMatch {
+        expected_version: Option<u64>,
+    },
 
     /// Play a card from your hand
     PlayCard {
-        card_index: usize,
-        chosen_suit: Option<CardSuit>, // Required for Whot card
+        card_id: u32,
+        chosen_suit: Option<CardSuit>, // Whot only; omit to choose with ChooseSuit
+        expected_version: Option<u64>,
+    },
+
+    /// Choose the demanded suit after playing a Whot card without one
+    ChooseSuit {
+        suit: CardSuit,
+        expected_version: Option<u64>,
     },
 
     /// Draw a card from the deck (when stuck or choosing to draw)
-    DrawCard,
+    DrawCard {
+        expected_version: Option<u64>,
+    },
 
     /// Call "Last Card!" when you have exactly 1 card
-    CallLastCard,
+    CallLastCard {
+        expected_version: Option<u64>,
+    },
+
+    /// Set standing orders the contract applies when your turn comes
+    SetAutoActions {
+        /// Draw automatically when you hold no legal card
+        auto_draw: bool,
+        /// Answer Pick Two with Pick Two (and Pick Three with Pick Three) automatically
+        auto_defend: bool,
+        /// Call "Last Card!" automatically when down to one card
+        auto_call_last_card: bool,
+        expected_version: Option<u64>,
+    },
 
     /// Challenge someone who forgot to call Last Card
     ChallengeLastCard {
         player_index: usize,
+        expected_version: Option<u64>,
     },
 
     /// Leave the match (forfeit)
-    LeaveMatch,
+    LeaveMatch {
+        expected_version: Option<u64>,
+    },
+
+    /// Mark yourself away; your seat is kept for the grace period
+    StepAway {
+        expected_version: Option<u64>,
+    },
+
+    /// Mark the idle turn holder away (other seated players)
+    MarkAway {
+        player_index: usize,
+        expected_version: Option<u64>,
+    },
+
+    /// Come back to your seat while it is away
+    Rejoin {
+        expected_version: Option<u64>,
+    },
+
+    /// Forfeit an away seat whose grace period has ended (other seated players)
+    ForfeitAway {
+        player_index: usize,
+        expected_version: Option<u64>,
+    },
+
+    /// Ask to pause the match; it pauses once every active player agrees
+    RequestPause {
+        expected_version: Option<u64>,
+    },
+
+    /// Agree to a pending pause request
+    AcceptPause {
+        expected_version: Option<u64>,
+    },
+
+    /// Agree to resume a paused match; it resumes once every active player agrees
+    Resume {
+        expected_version: Option<u64>,
+    },
+
+    /// Ask to undo the move you just made (unranked matches only)
+    RequestTakeback {
+        expected_version: Option<u64>,
+    },
+
+    /// Agree to the pending takeback; it applies once every opponent agrees
+    AcceptTakeback {
+        expected_version: Option<u64>,
+    },
+
+    /// Refuse the pending takeback (or withdraw your own); the move stands
+    DeclineTakeback {
+        expected_version: Option<u64>,
+    },
+
+    /// Authorize a session key for your seat, replacing any previous one
+    /// (the seat's owner only, never the session key itself)
+    AuthorizeSession {
+        session: SessionGrant,
+        expected_version: Option<u64>,
+    },
+
+    /// Revoke your seat's session key
+    RevokeSession {
+        expected_version: Option<u64>,
+    },
+
+    /// Register (or change) your persistent name at this table
+    ///
+    /// Profiles belong to this table application only; tournament and
+    /// matchmaking tables are separate applications and start without them.
+    RegisterProfile {
+        nickname: String,
+    },
+
+    /// Post a short text message or an emote to the match chat
+    /// (chat leaves the state version alone, so it never makes moves stale)
+    SendChat {
+        text: Option<String>,
+        emote: Option<Emote>,
+    },
 
     // Wave 4-5: Betting (placeholder)
     PlaceBet {
         player_index: usize,
         amount: u64,
+        expected_version: Option<u64>,
     },
+
+    // Cross-application API (see `LinotResponse`)
+    /// Set up a new match on this table with the given configuration
+    /// (host only; the table must be empty or its last match finished)
+    CreateMatch {
+        config: MatchConfig,
+        expected_version: Option<u64>,
+    },
+
+    /// Read the public match state; answered with `LinotResponse::PublicState`
+    GetPublicState,
+
+    /// Read the match result; answered with `LinotResponse::Result`
+    GetResult,
+}
+
+impl Operation {
+    /// State version the client read before submitting, if it sent one.
+    /// The contract rejects the operation if the match has moved on since.
+    pub fn expected_version(&self) -> Option<u64> {
+        match self {
+            Operation::JoinMatch { expected_version, .. }
+            | Operation::AddBot { expected_version, .. }
+            | Operation::SetReady { expected_version, .. }
+            | Operation::ExpireLobby { expected_version }
+            | Operation::StartMatch { expected_version }
+            | Operation::PlayCard { expected_version, .. }
+            | Operation::ChooseSuit { expected_version, .. }
+            | Operation::DrawCard { expected_version }
+            | Operation::CallLastCard { expected_version }
+            | Operation::SetAutoActions { expected_version, .. }
+            | Operation::ChallengeLastCard { expected_version, .. }
+            | Operation::LeaveMatch { expected_version }
+            | Operation::StepAway { expected_version }
+            | Operation::MarkAway { expected_version, .. }
+            | Operation::Rejoin { expected_version }
+            | Operation::ForfeitAway { expected_version, .. }
+            | Operation::RequestPause { expected_version }
+            | Operation::AcceptPause { expected_version }
+            | Operation::Resume { expected_version }
+            | Operation::RequestTakeback { expected_version }
+            | Operation::AcceptTakeback { expected_version }
+            | Operation::DeclineTakeback { expected_version }
+            | Operation::AuthorizeSession { expected_version, .. }
+            | Operation::RevokeSession { expected_version }
+            | Operation::PlaceBet { expected_version, .. }
+            | Operation::CreateMatch { expected_version, .. } => *expected_version,
+            Operation::RegisterProfile { .. }
+            | Operation::SendChat { .. }
+            | Operation::GetPublicState
+            | Operation::GetResult => None,
+        }
+    }
+}
+
+// ============ Cross-Application API ============
+//
+// Other applications on the same chain drive a table with
+// `ContractRuntime::call_application` and the `Operation`s above:
+//
+// - `CreateMatch { config }` sets up a fresh match (the caller becomes host
+//   unless the config names one); players then join as usual.
+// - `GetPublicState` returns what any spectator may see (no hands).
+// - `GetResult` returns the result once the match is finished.
+//
+// Calls without an authenticated signer act as the calling application,
+// i.e. `AccountOwner::from(caller_application_id)`. Every finished match is
+// also emitted as a `MatchResult` on the `RESULTS_STREAM` event stream, so
+// applications on other chains can subscribe and react.
+
+/// Event stream carrying a `MatchResult` for every finished match
+pub const RESULTS_STREAM: &[u8] = b"results";
+
+/// Response to an operation or application call
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum LinotResponse {
+    /// The operation was applied
+    #[default]
+    Ok,
+    /// Answer to `GetPublicState`
+    PublicState(Box<PublicMatchState>),
+    /// Answer to `GetResult` (`None` until the match finishes)
+    Result(Option<MatchResult>),
+}
+
+/// Public view of a match, without any player's cards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicMatchState {
+    pub config: MatchConfig,
+    pub status: MatchStatus,
+    pub players: Vec<PublicSeat>,
+    pub current_player_index: usize,
+    pub top_card: Option<Card>,
+    pub active_shape_demand: Option<CardSuit>,
+    pub pending_penalty: u8,
+    pub deck_size: usize,
+    /// When a waiting table may be cancelled with `ExpireLobby`, if it has a lobby expiry
+    pub lobby_expires_at: Option<u64>,
+    /// Players who went out in a play-out match, 1st first
+    pub placements: Vec<AccountOwner>,
+    /// Players who left after the match started, first leaver first
+    pub left_order: Vec<AccountOwner>,
+    pub version: u64,
+}
+
+/// A seat as seen by other applications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicSeat {
+    pub owner: AccountOwner,
+    pub nickname: String,
+    pub card_count: usize,
+    pub is_active: bool,
+    pub bot: Option<BotDifficulty>,
+    pub ready: bool,
+    pub away: bool,
+}
+
+/// How a finished match ended
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, async_graphql::SimpleObject)]
+pub struct MatchResult {
+    /// Winning player, winning partnership, or the players sharing a draw
+    pub winners: Vec<AccountOwner>,
+    /// Every seat in finishing order, 1st first (play-out placements, or the
+    /// winners followed by the rest by lowest hand total)
+    pub placements: Vec<AccountOwner>,
+    /// Whether the match was drawn
+    pub drawn: bool,
+    /// Winning team in a partnership match
+    pub winning_team: Option<u8>,
+    /// Hand point totals when the match finished, by seat
+    pub final_tally: Vec<u32>,
+    /// State version at which the match finished
+    pub version: u64,
+}
+
+// ============ Nicknames ============
+
+/// Shortest nickname, in characters
+pub const NICKNAME_MIN_LEN: usize = 2;
+
+/// Longest nickname, in characters
+pub const NICKNAME_MAX_LEN: usize = 20;
+
+/// Words no nickname may contain, compared case-insensitively word by word
+/// (so nobody can pass for a bot seat or the table staff)
+pub const RESERVED_NICKNAME_WORDS: &[&str] =
+    &["admin", "bot", "host", "linot", "moderator", "mod", "system"];
+
+/// Why a nickname was rejected
+#[derive(Debug, Error)]
+pub enum NicknameError {
+    #[error("must be {NICKNAME_MIN_LEN} to {NICKNAME_MAX_LEN} characters")]
+    Length,
+
+    #[error("may only contain ASCII letters, digits, single spaces and _ - .")]
+    Charset,
+
+    #[error("\"{0}\" is reserved")]
+    Reserved(String),
+}
+
+/// Check a nickname against the naming rules, returning it trimmed
+///
+/// Shared by the Linot contract and the applications built on it, so a name
+/// accepted by one is accepted by all.
+pub fn validate_nickname(nickname: &str) -> Result<String, NicknameError> {
+    let nickname = nickname.trim();
+
+    let length = nickname.chars().count();
+    if !(NICKNAME_MIN_LEN..=NICKNAME_MAX_LEN).contains(&length) {
+        return Err(NicknameError::Length);
+    }
+
+    // ASCII only, so look-alike letters from other scripts cannot imitate a
+    // name that `nickname_key` would tell apart
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.');
+    if !nickname.chars().all(allowed) || nickname.contains("  ") {
+        return Err(NicknameError::Charset);
+    }
+
+    let reserved = nickname
+        .split([' ', '_', '-', '.'])
+        .map(str::to_lowercase)
+        .find(|word| RESERVED_NICKNAME_WORDS.contains(&word.as_str()));
+    if let Some(word) = reserved {
+        return Err(NicknameError::Reserved(word));
+    }
+
+    Ok(nickname.to_string())
+}
+
+/// Key under which a nickname is compared for uniqueness
+pub fn nickname_key(nickname: &str) -> String {
+    nickname.trim().to_lowercase()
+}
+
+// ============ Session Keys ============
+
+/// What a session key may do for its seat
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
+pub enum SessionScope {
+    /// Card play only: play, choose a suit, draw, Last Card calls and
+    /// challenges, and standing orders
+    Moves,
+    /// Everything a seated player does at the table, on top of moves:
+    /// readiness, starting, away and rejoin, pause, takebacks and chat
+    Table,
+}
+
+impl SessionScope {
+    /// Whether a key with this scope may submit `operation` for its seat
+    ///
+    /// Joining, leaving, betting, profiles, match set-up and managing
+    /// session keys always need the owner's own signature.
+    pub fn allows(&self, operation: &Operation) -> bool {
+        let is_move = matches!(
+            operation,
+            Operation::PlayCard { .. }
+                | Operation::ChooseSuit { .. }
+                | Operation::DrawCard { .. }
+                | Operation::CallLastCard { .. }
+                | Operation::ChallengeLastCard { .. }
+                | Operation::SetAutoActions { .. }
+        );
+        let is_table = matches!(
+            operation,
+            Operation::SetReady { .. }
+                | Operation::StartMatch { .. }
+                | Operation::StepAway { .. }
+                | Operation::MarkAway { .. }
+                | Operation::Rejoin { .. }
+                | Operation::ForfeitAway { .. }
+                | Operation::RequestPause { .. }
+                | Operation::AcceptPause { .. }
+                | Operation::Resume { .. }
+                | Operation::RequestTakeback { .. }
+                | Operation::AcceptTakeback { .. }
+                | Operation::DeclineTakeback { .. }
+                | Operation::SendChat { .. }
+        );
+        match self {
+            SessionScope::Moves => is_move,
+            SessionScope::Table => is_move || is_table,
+        }
+    }
+}
+
+/// A session key a seated player authorizes to act for their seat
+///
+/// The key is an ordinary account (typically a throwaway key pair kept by
+/// the client), so moves can be signed without prompting the main wallet.
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::InputObject)]
+pub struct SessionGrant {
+    /// Account that will sign for the seat
+    pub key: AccountOwner,
+    /// How long the key works, in seconds
+    pub lifetime_secs: u64,
+    /// What the key may do
+    pub scope: SessionScope,
 }
 
 // ============ Messages (Cross-Chain Communication) ============
@@ -181,3 +930,63 @@ pub enum Message {
         top_card: Card,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_code_names_the_variant() {
+        assert_eq!(LinotError::NotYourTurn.code(), "NotYourTurn");
+        assert_eq!(LinotError::CardNotInHand(7).code(), "CardNotInHand");
+        let stale = LinotError::StaleVersion {
+            expected: 1,
+            current: 2,
+        };
+        assert_eq!(stale.code(), "StaleVersion");
+        assert_eq!(LinotError::from(NicknameError::Length).code(), "InvalidNickname");
+    }
+
+    #[test]
+    fn nickname_is_trimmed() {
+        assert_eq!(validate_nickname("  Ada L. Byron ").unwrap(), "Ada L. Byron");
+    }
+
+    #[test]
+    fn nickname_length_is_counted_after_trimming() {
+        assert!(matches!(validate_nickname(" a "), Err(NicknameError::Length)));
+        assert!(validate_nickname("ab").is_ok());
+        assert!(validate_nickname(&"x".repeat(NICKNAME_MAX_LEN)).is_ok());
+        assert!(matches!(
+            validate_nickname(&"x".repeat(NICKNAME_MAX_LEN + 1)),
+            Err(NicknameError::Length)
+        ));
+    }
+
+    #[test]
+    fn nickname_allows_only_ascii_and_separators() {
+        assert!(validate_nickname("card_shark-99").is_ok());
+        assert!(matches!(validate_nickname("Ada  Byron"), Err(NicknameError::Charset)));
+        assert!(matches!(validate_nickname("Ada!"), Err(NicknameError::Charset)));
+        // Cyrillic "а" looks like the Latin "a"
+        assert!(matches!(validate_nickname("Аlice"), Err(NicknameError::Charset)));
+        assert!(matches!(validate_nickname("Zoë"), Err(NicknameError::Charset)));
+    }
+
+    #[test]
+    fn nickname_rejects_reserved_words() {
+        assert!(matches!(
+            validate_nickname("Chat_Admin"),
+            Err(NicknameError::Reserved(word)) if word == "admin"
+        ));
+        assert!(matches!(validate_nickname("Easy Bot 2"), Err(NicknameError::Reserved(_))));
+        // Only whole words are reserved
+        assert!(validate_nickname("Abbott").is_ok());
+    }
+
+    #[test]
+    fn nickname_key_ignores_case_and_padding() {
+        assert_eq!(nickname_key(" Alice "), nickname_key("aLiCe"));
+        assert_ne!(nickname_key("Alice"), nickname_key("Alice2"));
+    }
+}
@@ -1,15 +1,33 @@
 #![cfg_attr(target_arch = "wasm32", no_main)]
 
+#[allow(dead_code)] // Shared with the contract; the service only uses move evaluation
+mod bot;
+#[allow(dead_code)] // Shared with the contract; the service only uses the read-only checks
+mod game_engine;
 mod state;
 
 use async_graphql::{Context, EmptySubscription, Object, Request, Response, Schema};
 use linera_sdk::{
-    abi::WithServiceAbi, linera_base_types::AccountOwner, views::View, Service, ServiceRuntime,
+    abi::WithServiceAbi,
+    linera_base_types::{AccountOwner, CryptoHash},
+    views::View,
+    Service, ServiceRuntime,
 };
 use std::sync::Arc;
 
-use crate::state::{LinotState, MatchConfig, MatchData, MatchStatus};
-use linot::{Card, CardSuit, LinotAbi, Operation};
+use crate::bot::{SeatView, Strategy, Tactician};
+use crate::game_engine::GameEngine;
+use crate::state::{
+    AutoActions, ChatMessage, LinotState, Profile, SessionKey, MatchConfig, MatchData, MatchEvent, MatchEventKind, MatchStatus,
+    TurnPhase,
+};
+use linot::{
+    BotDifficulty, Card, CardSuit, Emote, LinotAbi, LinotError, MatchResult, Operation, SessionGrant,
+    SessionScope,
+};
+
+/// Most events `changesSince` replays before sending a full snapshot instead
+const MAX_SYNC_EVENTS: usize = 256;
 
 /// GraphQL service for querying Linot match state
 pub struct LinotService {
@@ -37,6 +55,8 @@ impl Service for LinotService {
     }
 
     async fn handle_query(&self, request: Request) -> Response {
+        // Services answer one request at a time, so live updates come from the
+        // node's `notifications(chainId)` subscription; see `notifications`
         let schema = Schema::build(
             QueryRoot,
             MutationRoot,
@@ -61,11 +81,17 @@ impl QueryRoot {
         state.config.get().clone()
     }
 
-    /// Get the full match state (for debugging/trusted UIs)
-    /// WARNING: This exposes all player hands! Use player_view for production
-    async fn match_state(&self, ctx: &Context<'_>) -> MatchData {
+    /// Get the full match state with hidden cards removed
+    ///
+    /// Every hand except `viewer`'s (a seat owner or its session key) comes
+    /// back empty, and so does the deck; `cardCount` and `deckSize` still
+    /// give their sizes. Use `playerView` for a seat's own view of the table.
+    async fn match_state(&self, ctx: &Context<'_>, viewer: Option<AccountOwner>) -> MatchData {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
-        state.match_data.get().clone()
+        let mut match_data = state.match_data.get().clone();
+        let viewer_index = viewer.and_then(|owner| viewer_seat(&match_data, owner));
+        redact_hidden_cards(&mut match_data, viewer_index);
+        match_data
     }
 
     /// Get match status
@@ -74,6 +100,15 @@ impl QueryRoot {
         state.match_data.get().status
     }
 
+    /// Get when a waiting table may be cancelled (microseconds), if it has a lobby expiry
+    async fn lobby_expires_at(&self, ctx: &Context<'_>) -> Option<u64> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let secs = state.config.get().lobby_expiry_secs?;
+        (match_data.status == MatchStatus::Waiting)
+            .then(|| match_data.created_at.saturating_add(secs.saturating_mul(1_000_000)))
+    }
+
     /// Get the current player's index
     async fn current_player_index(&self, ctx: &Context<'_>) -> usize {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
@@ -114,6 +149,18 @@ impl QueryRoot {
         state.match_data.get().pending_penalty
     }
 
+    /// Get the state version (pass it back as expectedVersion on mutations)
+    async fn version(&self, ctx: &Context<'_>) -> u64 {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        state.match_data.get().version
+    }
+
+    /// Get the current turn phase (play, or choose a suit after Whot)
+    async fn turn_phase(&self, ctx: &Context<'_>) -> TurnPhase {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        state.match_data.get().turn_phase
+    }
+
     /// Get all players (with cards hidden for other players)
     async fn players(&self, ctx: &Context<'_>) -> Vec<PublicPlayer> {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
@@ -122,12 +169,17 @@ impl QueryRoot {
         match_data
             .players
             .iter()
-            .map(|p| PublicPlayer {
+            .enumerate()
+            .map(|(i, p)| PublicPlayer {
                 owner: p.owner,
                 nickname: p.nickname.clone(),
                 card_count: p.card_count,
                 is_active: p.is_active,
                 called_last_card: p.called_last_card,
+                bot: p.bot,
+                team: GameEngine::team_of(match_data, i),
+                ready: p.ready,
+                away_since: p.away_since,
             })
             .collect()
     }
@@ -137,38 +189,82 @@ impl QueryRoot {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
         let match_data = state.match_data.get();
 
-        // Find the requesting player
-        let player_data = match_data.players.iter().find(|p| p.owner == player)?;
+        // Find the requesting player (or the seat a session key acts for)
+        let seat = viewer_seat(match_data, player)?;
+        Some(player_view_at(state, Some(seat)))
+    }
 
-        // Build opponent info (without cards)
-        let opponents: Vec<PublicPlayer> = match_data
-            .players
-            .iter()
-            .filter(|p| p.owner != player)
-            .map(|p| PublicPlayer {
-                owner: p.owner,
-                nickname: p.nickname.clone(),
-                card_count: p.card_count,
-                is_active: p.is_active,
-                called_last_card: p.called_last_card,
+    /// Suggest a move for a player, ranking their legal plays with reasons
+    async fn suggest_move(&self, ctx: &Context<'_>, player: AccountOwner) -> Option<MoveSuggestion> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+
+        if match_data.status != MatchStatus::InProgress {
+            return None;
+        }
+        // Find the requesting player (or the seat a session key acts for)
+        let seat = viewer_seat(match_data, player)?;
+        let view = SeatView::new(match_data, seat)?;
+        let is_your_turn = match_data.current_player_index == seat;
+
+        // A Whot is waiting for its suit: that is the only decision to make
+        if match_data.turn_phase == TurnPhase::ChooseSuit {
+            let suit = match_data
+                .discard_pile
+                .last()
+                .map(|whot| Tactician.choose_suit(&view, whot));
+            return Some(MoveSuggestion {
+                is_your_turn,
+                moves: Vec::new(),
+                draw_recommended: false,
+                suit_to_choose: suit,
+                summary: "Choose the suit you hold the most points in".to_string(),
+            });
+        }
+
+        let moves: Vec<RankedMove> = bot::evaluate_moves(&view)
+            .into_iter()
+            .map(|evaluation| RankedMove {
+                card: evaluation.card.clone(),
+                chosen_suit: evaluation.chosen_suit,
+                score: evaluation.score,
+                reasons: evaluation.reasons,
             })
             .collect();
+        let draw_recommended = moves.is_empty();
+        let summary = match moves.first() {
+            Some(best) => best
+                .reasons
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "Best available play".to_string()),
+            None if match_data.pending_penalty > 0 => format!(
+                "No defence in hand: draw the {} penalty cards",
+                match_data.pending_penalty
+            ),
+            None => "No legal card: draw from the market".to_string(),
+        };
 
-        Some(PlayerView {
-            my_cards: player_data.cards.clone(),
-            my_card_count: player_data.card_count,
-            called_last_card: player_data.called_last_card,
-            opponents,
-            top_card: match_data.discard_pile.last().cloned(),
-            deck_size: match_data.deck.len(),
-            current_player_index: match_data.current_player_index,
-            status: match_data.status,
-            active_shape_demand: match_data.active_shape_demand,
-            pending_penalty: match_data.pending_penalty,
-            winner_index: match_data.winner_index,
+        Some(MoveSuggestion {
+            is_your_turn,
+            moves,
+            draw_recommended,
+            suit_to_choose: None,
+            summary,
         })
     }
 
+    /// Hash an invite code, for `MatchConfig.inviteCodeHash` when setting up a private table
+    async fn invite_code_hash(&self, code: String) -> CryptoHash {
+        linot::hash_invite_code(&code)
+    }
+
+    /// Get how the match ended (once finished), as reported to other applications
+    async fn match_result(&self, ctx: &Context<'_>) -> Option<MatchResult> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        GameEngine::match_result(state.match_data.get())
+    }
+
     /// Get the winner (if match is finished)
     async fn winner(&self, ctx: &Context<'_>) -> Option<AccountOwner> {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
@@ -180,6 +276,266 @@ impl QueryRoot {
             .map(|p| p.owner)
     }
 
+    /// Get match events in order, starting at `offset`
+    ///
+    /// Card IDs of dealt and drawn cards are only shown to the player who
+    /// received them (pass `viewer`, your account or session key, to see
+    /// your own).
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+        viewer: Option<AccountOwner>,
+    ) -> Vec<MatchEvent> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let viewer_index = viewer.and_then(|owner| viewer_seat(match_data, owner));
+
+        let count = state.events.count();
+        let start = offset.unwrap_or(0).min(count);
+        let end = limit.map_or(count, |limit| start.saturating_add(limit).min(count));
+        let mut events = state
+            .events
+            .read(start..end)
+            .await
+            .expect("Failed to read events");
+
+        redact_events(&mut events, viewer_index);
+        events
+    }
+
+    /// Get what changed since `since_version`, the last version the client saw
+    ///
+    /// Returns the public events in order, the viewer's own hand changes and
+    /// the current turn summary. When the changes cannot be replayed (too
+    /// many of them, a takeback, a new match on the table, or an unknown
+    /// version) a full `snapshot` is sent instead; spectators get one with an
+    /// empty hand.
+    async fn changes_since(
+        &self,
+        ctx: &Context<'_>,
+        since_version: u64,
+        viewer: Option<AccountOwner>,
+    ) -> StateSync {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let viewer_index = viewer.and_then(|owner| viewer_seat(match_data, owner));
+
+        // Events are logged in version order, so find the first newer one
+        let count = state.events.count();
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let event = state.events.get(mid).await.expect("Failed to read events");
+            if event.is_some_and(|event| event.version <= since_version) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let in_range = since_version <= match_data.version && count - low <= MAX_SYNC_EVENTS;
+        let mut events = if in_range {
+            state
+                .events
+                .read(low..count)
+                .await
+                .expect("Failed to read events")
+        } else {
+            Vec::new()
+        };
+        let replayable = in_range
+            && !events.iter().any(|event| {
+                matches!(event.kind, MatchEventKind::TakebackApplied | MatchEventKind::MatchCreated)
+            });
+
+        if !replayable {
+            events.clear();
+        }
+        redact_events(&mut events, viewer_index);
+
+        // Net change to the viewer's hand
+        let mut added: Vec<u32> = Vec::new();
+        let mut removed: Vec<u32> = Vec::new();
+        for event in events.iter().filter(|event| viewer_index.is_some() && event.player_index == viewer_index) {
+            match event.kind {
+                MatchEventKind::CardsDealt | MatchEventKind::CardsDrawn => added.extend(&event.card_ids),
+                MatchEventKind::CardPlayed => {
+                    for id in &event.card_ids {
+                        match added.iter().position(|added| added == id) {
+                            Some(position) => {
+                                added.remove(position);
+                            }
+                            None => removed.push(*id),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        let hand = viewer_index.map_or(&[][..], |seat| &match_data.players[seat].cards[..]);
+
+        StateSync {
+            from_version: since_version,
+            version: match_data.version,
+            events,
+            hand_added: hand.iter().filter(|card| added.contains(&card.id)).cloned().collect(),
+            hand_removed: removed,
+            status: match_data.status,
+            current_player_index: match_data.current_player_index,
+            turn_phase: match_data.turn_phase,
+            top_card: match_data.discard_pile.last().cloned(),
+            deck_size: match_data.deck.len(),
+            active_shape_demand: match_data.active_shape_demand,
+            pending_penalty: match_data.pending_penalty,
+            snapshot: (!replayable).then(|| player_view_at(state, viewer_index)),
+        }
+    }
+
+    /// Get live-update notifications logged after `after`, an offset into the
+    /// event log (pass the previous batch's `nextOffset`)
+    ///
+    /// Re-run this whenever the node's `notifications(chainId)` subscription
+    /// reports a new block on the match chain instead of polling `playerView`.
+    /// An application service answers each query against a snapshot of the
+    /// chain and cannot keep a stream open, so that node subscription is the
+    /// push signal and this query is the pull that follows it. Notifications
+    /// only carry public details, so no hand ever leaks; `viewer` (a seat
+    /// owner or its session key) sets `forYou` on those about the viewer's
+    /// own seat.
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        viewer: Option<AccountOwner>,
+        after: Option<usize>,
+        limit: Option<usize>,
+    ) -> NotificationBatch {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let viewer_index = viewer.and_then(|owner| viewer_seat(match_data, owner));
+
+        let count = state.events.count();
+        let start = after.unwrap_or(0).min(count);
+        let end = limit.map_or(count, |limit| start.saturating_add(limit).min(count));
+        let events = state
+            .events
+            .read(start..end)
+            .await
+            .expect("Failed to read events");
+
+        let notifications = events
+            .into_iter()
+            .filter_map(|event| {
+                let kind = match event.kind {
+                    MatchEventKind::TurnChanged => NotificationKind::TurnChanged,
+                    MatchEventKind::CardPlayed => NotificationKind::CardPlayed,
+                    MatchEventKind::PenaltyApplied => NotificationKind::PenaltyApplied,
+                    MatchEventKind::MatchFinished => NotificationKind::MatchFinished,
+                    _ => return None,
+                };
+                Some(Notification {
+                    version: event.version,
+                    kind,
+                    player_index: event.player_index,
+                    card_id: event.card_ids.first().copied().filter(|_| !event.is_private()),
+                    suit: event.suit,
+                    penalty_cards: event.penalty_cards,
+                    for_you: viewer_index.is_some() && event.player_index == viewer_index,
+                })
+            })
+            .collect();
+
+        NotificationBatch {
+            notifications,
+            next_offset: end,
+            version: match_data.version,
+        }
+    }
+
+    /// Get a player's registered profile, if any
+    async fn profile(&self, ctx: &Context<'_>, owner: AccountOwner) -> Option<Profile> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        state.profiles.get(&owner).await.ok().flatten()
+    }
+
+    /// Check a nickname against the naming rules and registered names
+    /// (returns the reason it would be rejected, or None if it is free)
+    async fn nickname_problem(&self, ctx: &Context<'_>, nickname: String, owner: Option<AccountOwner>) -> Option<String> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let nickname = match linot::validate_nickname(&nickname) {
+            Ok(nickname) => nickname,
+            Err(e) => return Some(e.to_string()),
+        };
+        let holder = state
+            .profile_names
+            .get(&linot::nickname_key(&nickname))
+            .await
+            .ok()
+            .flatten();
+        (holder.is_some() && holder != owner).then(|| "already registered".to_string())
+    }
+
+    /// Get the match chat, oldest first, one page at a time
+    async fn chat(
+        &self,
+        ctx: &Context<'_>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> Vec<ChatMessage> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let count = state.chat.count();
+        let start = offset.unwrap_or(0).min(count);
+        let end = limit.map_or(count, |limit| start.saturating_add(limit).min(count));
+        state
+            .chat
+            .read(start..end)
+            .await
+            .expect("Failed to read chat")
+    }
+
+    /// Audit the match for card conservation and internal consistency
+    async fn audit_match(&self, ctx: &Context<'_>) -> AuditReport {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let violations = GameEngine::audit(match_data);
+
+        AuditReport {
+            ok: violations.is_empty(),
+            violations,
+            version: match_data.version,
+        }
+    }
+
+    /// Get how the betting pool is split by the match result (once finished)
+    ///
+    /// The winner takes the pool; a winning team or a draw splits it evenly,
+    /// and play-out placements split it by the configured prize split.
+    async fn payouts(&self, ctx: &Context<'_>) -> Vec<Payout> {
+        let state = ctx.data_unchecked::<Arc<LinotState>>();
+        let match_data = state.match_data.get();
+        let pool = state
+            .betting_pool
+            .get()
+            .as_ref()
+            .map(|pool| pool.total_pool)
+            .unwrap_or(0);
+
+        GameEngine::final_result(match_data)
+            .map(|result| GameEngine::payout_shares(&result, pool, &state.config.get().prize_split))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(player_index, amount)| {
+                let player = match_data.players.get(player_index)?;
+                Some(Payout {
+                    player_index,
+                    owner: player.owner,
+                    amount,
+                })
+            })
+            .collect()
+    }
+
     /// Get betting pool total (Wave 4-5 feature)
     async fn betting_pool_total(&self, ctx: &Context<'_>) -> u64 {
         let state = ctx.data_unchecked::<Arc<LinotState>>();
@@ -202,6 +558,14 @@ struct PublicPlayer {
     card_count: usize,
     is_active: bool,
     called_last_card: bool,
+    /// Bot difficulty, if this seat is played by the contract
+    bot: Option<BotDifficulty>,
+    /// Team number in a partnership match
+    team: Option<u8>,
+    /// Confirmed present in the lobby
+    ready: bool,
+    /// When the player was marked away (microseconds), if they are
+    away_since: Option<u64>,
 }
 
 /// Player-specific view (includes player's cards, hides opponent cards)
@@ -213,6 +577,10 @@ struct PlayerView {
     my_card_count: usize,
     /// Whether you called last card
     called_last_card: bool,
+    /// Your standing orders
+    auto_actions: AutoActions,
+    /// Your team in a partnership match
+    my_team: Option<u8>,
     /// Opponent info (without their cards)
     opponents: Vec<PublicPlayer>,
     /// Top card in discard pile
@@ -227,83 +595,858 @@ struct PlayerView {
     active_shape_demand: Option<CardSuit>,
     /// Pending penalty cards
     pending_penalty: u8,
+    /// Current turn phase
+    turn_phase: TurnPhase,
     /// Winner index (if finished)
     winner_index: Option<usize>,
+    /// Winning team (if a partnership match finished)
+    winning_team: Option<u8>,
+    /// Seats in finishing order, 1st first (play-out matches)
+    placements: Vec<usize>,
+    /// Players sharing a drawn result (if finished without a winner)
+    tied_players: Vec<usize>,
+    /// Hand point totals when the match finished, by seat
+    final_tally: Vec<u32>,
+    /// Seats agreeing to the pending pause (or, while paused, to resuming)
+    pause_votes: Vec<usize>,
+    /// Seat asking to take back its last move, if any
+    takeback_request: Option<usize>,
+    /// Whether you may request a takeback of your last move now
+    can_take_back: bool,
+    /// Your seat's session key, if one is authorized
+    session: Option<SessionKey>,
+    /// State version this view was taken at
+    version: u64,
+}
+
+/// What a live-update notification is about
+#[derive(async_graphql::Enum, Clone, Copy, PartialEq, Eq)]
+enum NotificationKind {
+    /// The turn passed to `playerIndex`
+    TurnChanged,
+    /// `playerIndex` played `cardId`
+    CardPlayed,
+    /// `playerIndex` owes `penaltyCards` cards
+    PenaltyApplied,
+    /// The match finished (`playerIndex` is the winner, if there is one)
+    MatchFinished,
+}
+
+/// A live-update notification, with public details only
+#[derive(async_graphql::SimpleObject)]
+struct Notification {
+    /// State version the change produced
+    version: u64,
+    /// What happened
+    kind: NotificationKind,
+    /// Seat concerned
+    player_index: Option<usize>,
+    /// Card played
+    card_id: Option<u32>,
+    /// Suit demanded with a Whot card
+    suit: Option<CardSuit>,
+    /// Cards owed as a penalty
+    penalty_cards: u8,
+    /// Whether the notification concerns the viewer's own seat
+    for_you: bool,
+}
+
+/// Notifications since the client's last read
+#[derive(async_graphql::SimpleObject)]
+struct NotificationBatch {
+    /// Notifications in the order they happened
+    notifications: Vec<Notification>,
+    /// Offset to pass as `after` next time
+    next_offset: usize,
+    /// Current state version
+    version: u64,
+}
+
+/// Result of a mutation
+#[derive(async_graphql::SimpleObject)]
+struct MoveOutcome {
+    /// Whether the operation passed the checks and was scheduled
+    scheduled: bool,
+    /// Whether the checks knew who is acting; without `player` a move is
+    /// checked as the current turn holder's and seat checks are skipped, so
+    /// those rejections only show when the block executes
+    turn_verified: bool,
+    /// Why the contract would reject the operation
+    error: Option<String>,
+    /// `LinotError` variant naming the reason, e.g. `NotYourTurn`
+    error_code: Option<String>,
+}
+
+/// Check an operation the way the contract will, and schedule it only if it passes
+fn schedule_checked(ctx: &Context<'_>, player: Option<AccountOwner>, operation: Operation) -> MoveOutcome {
+    let state = ctx.data_unchecked::<Arc<LinotState>>();
+    let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
+
+    let turn_verified = player.is_some();
+    if let Err(error) = preflight(state, runtime, player, &operation) {
+        return MoveOutcome {
+            scheduled: false,
+            turn_verified,
+            error: Some(error.to_string()),
+            error_code: Some(error.code().to_string()),
+        };
+    }
+
+    runtime.schedule_operation(&operation);
+    MoveOutcome {
+        scheduled: true,
+        turn_verified,
+        error: None,
+        error_code: None,
+    }
+}
+
+/// Run the contract's checks for an operation against a copy of the match
+///
+/// `player` may be a seat owner or its session key; without it the current
+/// turn holder is assumed to sign a move (reported as `turn_verified: false`).
+/// Other operations go through `check_table_action`.
+fn preflight(
+    state: &LinotState,
+    runtime: &ServiceRuntime<LinotService>,
+    player: Option<AccountOwner>,
+    operation: &Operation,
+) -> Result<(), LinotError> {
+    let mut preview = state.match_data.get().clone();
+    let mut events = Vec::new();
+
+    // Reject operations built on a stale view of the match
+    if let Some(expected) = operation.expected_version() {
+        if expected != preview.version {
+            return Err(LinotError::StaleVersion {
+                expected,
+                current: preview.version,
+            });
+        }
+    }
+
+    // Resolve the signer to the seat owner it acts for
+    let signer = match player {
+        Some(player) => match viewer_seat(&preview, player) {
+            Some(seat) => {
+                let seated = &preview.players[seat];
+                if let Some(session) = seated.session.as_ref().filter(|_| seated.owner != player) {
+                    if runtime.system_time().micros() >= session.expires_at {
+                        return Err(LinotError::SessionKeyExpired);
+                    }
+                    if !session.scope.allows(operation) {
+                        return Err(LinotError::SessionNotPermitted);
+                    }
+                }
+                Some(seated.owner)
+            }
+            None => Some(player),
+        },
+        None => None,
+    };
+    // Moves without a signer are checked as if the turn holder made them;
+    // nobody is seated yet, so no match can be in progress
+    let caller = signer.or_else(|| preview.players.get(preview.current_player_index).map(|p| p.owner));
+    let check_turn = |preview: &MatchData| match caller {
+        Some(caller) => GameEngine::check_turn(preview, caller),
+        None => Err(LinotError::MatchNotInProgress),
+    };
+
+    match operation {
+        Operation::PlayCard { card_id, chosen_suit, .. } => {
+            check_turn(&preview)?;
+            GameEngine::play_card(&mut preview, *card_id, *chosen_suit, &mut events)
+        }
+        Operation::ChooseSuit { suit, .. } => {
+            check_turn(&preview)?;
+            GameEngine::choose_suit(&mut preview, *suit, &mut events)
+        }
+        Operation::DrawCard { .. } => {
+            check_turn(&preview)?;
+            let application_id = runtime.application_id().forget_abi();
+            let seed = GameEngine::match_seed(runtime.chain_id(), application_id, preview.match_number);
+            GameEngine::draw_card(&mut preview, &seed, &mut events)
+        }
+        Operation::CallLastCard { .. } if preview.status == MatchStatus::Paused => Err(LinotError::MatchPaused),
+        operation => check_table_action(state, runtime, signer, &preview, operation),
+    }
+}
+
+/// Run the contract's status and seat checks for an operation other than a move
+///
+/// Checks that depend on who is acting are skipped without a `signer`, and
+/// so are the ones that need more than the match state (nickname and
+/// profile lookups, chat rate limits, seat reservations).
+fn check_table_action(
+    state: &LinotState,
+    runtime: &ServiceRuntime<LinotService>,
+    signer: Option<AccountOwner>,
+    match_data: &MatchData,
+    operation: &Operation,
+) -> Result<(), LinotError> {
+    let config = state.config.get();
+    let now = runtime.system_time().micros();
+    let status = match_data.status;
+    let lobby_expired = config.lobby_expiry_secs.is_some_and(|secs| {
+        status == MatchStatus::Waiting
+            && now >= match_data.created_at.saturating_add(secs.saturating_mul(1_000_000))
+    });
+
+    // Seat of the signer, if it is still playing; passes when the signer is unknown
+    let active_seat = || -> Result<Option<usize>, LinotError> {
+        match signer {
+            Some(signer) => match_data
+                .players
+                .iter()
+                .position(|p| p.owner == signer && p.is_active)
+                .map(Some)
+                .ok_or(LinotError::NotAPlayer),
+            None => Ok(None),
+        }
+    };
+    let seated = signer.map(|signer| match_data.players.iter().position(|p| p.owner == signer));
+    let in_progress = || match status {
+        MatchStatus::InProgress => Ok(()),
+        MatchStatus::Paused => Err(LinotError::MatchPaused),
+        _ => Err(LinotError::MatchNotInProgress),
+    };
+    let lobby_open = || {
+        if status != MatchStatus::Waiting {
+            return Err(LinotError::MatchAlreadyStarted);
+        }
+        if lobby_expired {
+            return Err(LinotError::LobbyExpired);
+        }
+        Ok(())
+    };
+
+    match operation {
+        Operation::JoinMatch { .. } => {
+            lobby_open()?;
+            if match_data.players.len() >= config.max_players as usize {
+                return Err(LinotError::MatchFull(config.max_players));
+            }
+            if seated.flatten().is_some() {
+                return Err(LinotError::PlayerAlreadyJoined);
+            }
+        }
+        Operation::AddBot { .. } => {
+            if signer.is_some() && config.host != signer {
+                return Err(LinotError::OnlyHostCanAddBots);
+            }
+            if config.is_ranked {
+                return Err(LinotError::BotsNotAllowed);
+            }
+            lobby_open()?;
+            if match_data.players.len() >= config.max_players as usize {
+                return Err(LinotError::MatchFull(config.max_players));
+            }
+        }
+        Operation::SetReady { .. } => {
+            lobby_open()?;
+            if seated == Some(None) {
+                return Err(LinotError::NotAPlayer);
+            }
+        }
+        Operation::ExpireLobby { .. } => {
+            if !lobby_expired {
+                return Err(LinotError::LobbyNotExpired);
+            }
+        }
+        Operation::StartMatch { .. } => {
+            if seated == Some(None) {
+                return Err(LinotError::OnlyHostCanStart);
+            }
+            if match_data.players.is_empty() {
+                return Err(LinotError::NotEnoughPlayers(1));
+            }
+            lobby_open()?;
+            // Starting confirms the caller, so the count needs to know who that is
+            let not_ready = match_data
+                .players
+                .iter()
+                .filter(|p| p.is_active && !p.ready && Some(p.owner) != signer)
+                .count();
+            if signer.is_some() && not_ready > 0 {
+                return Err(LinotError::PlayersNotReady(not_ready));
+            }
+        }
+        Operation::SetAutoActions { .. } => {
+            if seated == Some(None) {
+                return Err(LinotError::NotAPlayer);
+            }
+        }
+        Operation::LeaveMatch { .. } => {
+            if matches!(status, MatchStatus::Finished | MatchStatus::Cancelled) {
+                return Err(LinotError::MatchNotInProgress);
+            }
+        }
+        Operation::StepAway { .. } => {
+            if status != MatchStatus::InProgress {
+                return Err(LinotError::MatchNotInProgress);
+            }
+            if let Some(seat) = active_seat()? {
+                if match_data.players[seat].away_since.is_some() {
+                    return Err(LinotError::AlreadyAway);
+                }
+            }
+        }
+        Operation::MarkAway { player_index, .. } => {
+            if status != MatchStatus::InProgress {
+                return Err(LinotError::MatchNotInProgress);
+            }
+            active_seat()?;
+            if *player_index >= match_data.players.len() {
+                return Err(LinotError::InvalidPlayerIndex(*player_index));
+            }
+            if *player_index != match_data.current_player_index {
+                return Err(LinotError::NotTurnHolder(*player_index));
+            }
+            let idle_since = match_data
+                .turn_started_at
+                .saturating_add(config.idle_turn_secs.saturating_mul(1_000_000));
+            if now < idle_since {
+                return Err(LinotError::TurnNotIdle);
+            }
+            if match_data.players[*player_index].away_since.is_some() {
+                return Err(LinotError::AlreadyAway);
+            }
+        }
+        Operation::Rejoin { .. } => {
+            if let Some(seat) = active_seat()? {
+                if match_data.players[seat].away_since.is_none() {
+                    return Err(LinotError::NotAway);
+                }
+            }
+        }
+        Operation::ForfeitAway { player_index, .. } => {
+            if status != MatchStatus::InProgress {
+                return Err(LinotError::MatchNotInProgress);
+            }
+            active_seat()?;
+            let player = match_data
+                .players
+                .get(*player_index)
+                .ok_or(LinotError::InvalidPlayerIndex(*player_index))?;
+            let away_since = player
+                .away_since
+                .filter(|_| player.is_active)
+                .ok_or(LinotError::NotAway)?;
+            let grace_end = away_since.saturating_add(config.grace_period_secs.saturating_mul(1_000_000));
+            if now < grace_end {
+                return Err(LinotError::GracePeriodRunning);
+            }
+        }
+        Operation::ChallengeLastCard { player_index, .. } => {
+            in_progress()?;
+            active_seat()?;
+            if *player_index >= match_data.players.len() {
+                return Err(LinotError::InvalidPlayerIndex(*player_index));
+            }
+        }
+        Operation::RequestPause { .. } | Operation::AcceptPause { .. } => {
+            in_progress()?;
+            let seat = active_seat()?;
+            if matches!(operation, Operation::AcceptPause { .. }) && match_data.pause_votes.is_empty() {
+                return Err(LinotError::NoPauseRequested);
+            }
+            if seat.is_some_and(|seat| match_data.pause_votes.contains(&seat)) {
+                return Err(LinotError::AlreadyAgreed);
+            }
+        }
+        Operation::Resume { .. } => {
+            if status != MatchStatus::Paused {
+                return Err(LinotError::MatchNotPaused);
+            }
+            if active_seat()?.is_some_and(|seat| match_data.pause_votes.contains(&seat)) {
+                return Err(LinotError::AlreadyAgreed);
+            }
+        }
+        Operation::RequestTakeback { .. } => {
+            if config.is_ranked {
+                return Err(LinotError::TakebacksDisabled);
+            }
+            if status != MatchStatus::InProgress {
+                return Err(LinotError::MatchNotInProgress);
+            }
+            if let Some(seat) = active_seat()? {
+                if state.takeback.get().as_ref().map(|point| point.seat) != Some(seat) {
+                    return Err(LinotError::NoTakeback);
+                }
+            }
+            if match_data.takeback_request.is_some() {
+                return Err(LinotError::AlreadyAgreed);
+            }
+        }
+        Operation::AcceptTakeback { .. } => {
+            let requester = match_data.takeback_request.ok_or(LinotError::NoTakebackRequested)?;
+            if active_seat()?.is_some_and(|seat| seat == requester || match_data.takeback_votes.contains(&seat)) {
+                return Err(LinotError::AlreadyAgreed);
+            }
+        }
+        Operation::DeclineTakeback { .. } => {
+            if match_data.takeback_request.is_none() {
+                return Err(LinotError::NoTakebackRequested);
+            }
+            active_seat()?;
+        }
+        Operation::AuthorizeSession { .. } => {
+            active_seat()?;
+        }
+        Operation::RevokeSession { .. } => {
+            if let Some(seat) = active_seat()? {
+                if match_data.players[seat].session.is_none() {
+                    return Err(LinotError::NoSessionKey);
+                }
+            }
+        }
+        Operation::RegisterProfile { nickname } => {
+            linot::validate_nickname(nickname)?;
+        }
+        Operation::SendChat { .. } => {
+            if seated == Some(None) && !config.spectator_chat {
+                return Err(LinotError::ChatNotAllowed);
+            }
+        }
+        Operation::CreateMatch { .. } => {
+            if signer.is_some() && config.host.is_some_and(|host| Some(host) != signer) {
+                return Err(LinotError::OnlyHostCanCreateMatch);
+            }
+            let empty = status == MatchStatus::Waiting && match_data.players.is_empty();
+            let over = matches!(status, MatchStatus::Finished | MatchStatus::Cancelled);
+            if !empty && !over {
+                return Err(LinotError::TableInUse);
+            }
+        }
+        Operation::PlaceBet { .. } => return Err(LinotError::BettingNotImplemented),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Seat a viewer sees the match from: theirs, or the one their session key acts for
+fn viewer_seat(match_data: &MatchData, viewer: AccountOwner) -> Option<usize> {
+    match_data.players.iter().position(|p| p.owner == viewer).or_else(|| {
+        match_data
+            .players
+            .iter()
+            .position(|p| p.session.as_ref().is_some_and(|s| s.key == viewer))
+    })
+}
+
+/// Empty every hand but the viewer's, and the deck
+fn redact_hidden_cards(match_data: &mut MatchData, viewer_index: Option<usize>) {
+    match_data.deck.clear();
+    for (seat, player) in match_data.players.iter_mut().enumerate() {
+        if Some(seat) != viewer_index {
+            player.cards.clear();
+        }
+    }
+}
+
+/// Changes since a client's last known version
+#[derive(async_graphql::SimpleObject)]
+struct StateSync {
+    /// Version the changes start from (the client's last known version)
+    from_version: u64,
+    /// Current state version
+    version: u64,
+    /// Public changes in order (empty when a snapshot is sent instead)
+    events: Vec<MatchEvent>,
+    /// Cards that entered your hand and are still in it
+    hand_added: Vec<Card>,
+    /// IDs of cards that were in your hand and have left it
+    hand_removed: Vec<u32>,
+    /// Match status
+    status: MatchStatus,
+    /// Index of current player
+    current_player_index: usize,
+    /// Current turn phase
+    turn_phase: TurnPhase,
+    /// Top card in discard pile
+    top_card: Option<Card>,
+    /// Cards remaining in deck
+    deck_size: usize,
+    /// Active shape demand (from Whot card)
+    active_shape_demand: Option<CardSuit>,
+    /// Pending penalty cards
+    pending_penalty: u8,
+    /// Full view, sent when the changes cannot be replayed
+    snapshot: Option<PlayerView>,
+}
+
+/// Build a seat's view of the match (a spectator's when `seat` is None)
+fn player_view_at(state: &LinotState, seat: Option<usize>) -> PlayerView {
+    let match_data = state.match_data.get();
+    let player_data = seat.map(|seat| &match_data.players[seat]);
+
+    // Build opponent info (without cards)
+    let opponents: Vec<PublicPlayer> = match_data
+        .players
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != seat)
+        .map(|(i, p)| PublicPlayer {
+            owner: p.owner,
+            nickname: p.nickname.clone(),
+            card_count: p.card_count,
+            is_active: p.is_active,
+            called_last_card: p.called_last_card,
+            bot: p.bot,
+            team: GameEngine::team_of(match_data, i),
+            ready: p.ready,
+            away_since: p.away_since,
+        })
+        .collect();
+
+    PlayerView {
+        my_cards: player_data.map(|p| p.cards.clone()).unwrap_or_default(),
+        my_card_count: player_data.map_or(0, |p| p.card_count),
+        called_last_card: player_data.is_some_and(|p| p.called_last_card),
+        auto_actions: player_data.map(|p| p.auto_actions).unwrap_or_default(),
+        my_team: seat.and_then(|seat| GameEngine::team_of(match_data, seat)),
+        opponents,
+        top_card: match_data.discard_pile.last().cloned(),
+        deck_size: match_data.deck.len(),
+        current_player_index: match_data.current_player_index,
+        status: match_data.status,
+        active_shape_demand: match_data.active_shape_demand,
+        pending_penalty: match_data.pending_penalty,
+        turn_phase: match_data.turn_phase,
+        winner_index: match_data.winner_index,
+        winning_team: match_data.winning_team,
+        placements: match_data.placements.clone(),
+        tied_players: match_data.tied_players.clone(),
+        final_tally: match_data.final_tally.clone(),
+        pause_votes: match_data.pause_votes.clone(),
+        takeback_request: match_data.takeback_request,
+        can_take_back: state
+            .takeback
+            .get()
+            .as_ref()
+            .is_some_and(|point| Some(point.seat) == seat && match_data.takeback_request.is_none()),
+        session: player_data.and_then(|p| p.session.clone()),
+        version: match_data.version,
+    }
+}
+
+/// Hide the card IDs of dealt and drawn cards from everyone but their receiver
+fn redact_events(events: &mut [MatchEvent], viewer_index: Option<usize>) {
+    for event in events.iter_mut() {
+        if event.is_private() && event.player_index != viewer_index {
+            event.card_ids.clear();
+        }
+    }
+}
+
+/// Move hint for a player
+#[derive(async_graphql::SimpleObject)]
+struct MoveSuggestion {
+    /// Whether it is currently this player's turn
+    is_your_turn: bool,
+    /// Legal plays, best first
+    moves: Vec<RankedMove>,
+    /// No legal play: draw from the market
+    draw_recommended: bool,
+    /// Suit to choose when a Whot is waiting for one
+    suit_to_choose: Option<CardSuit>,
+    /// One-line advice
+    summary: String,
+}
+
+/// A legal play with its heuristic score
+#[derive(async_graphql::SimpleObject)]
+struct RankedMove {
+    card: Card,
+    /// Suit to demand if the card is a Whot
+    chosen_suit: Option<CardSuit>,
+    /// Higher is better
+    score: i32,
+    /// Why this play scored as it did
+    reasons: Vec<String>,
+}
+
+/// Share of the betting pool paid to a player
+#[derive(async_graphql::SimpleObject)]
+struct Payout {
+    player_index: usize,
+    owner: AccountOwner,
+    amount: u64,
+}
+
+/// Result of auditing the match state
+#[derive(async_graphql::SimpleObject)]
+struct AuditReport {
+    /// Whether no violations were found
+    ok: bool,
+    /// Description of each violation
+    violations: Vec<String>,
+    /// State version that was audited
+    version: u64,
 }
 
 // ============ GraphQL Mutation Root ============
 
 /// GraphQL mutation root for game actions
+///
+/// Every mutation runs the contract's checks first and returns a
+/// `MoveOutcome`; it is only scheduled if those checks pass. Pass `player`
+/// (your account or session key) so the checks that depend on who is acting
+/// can run; the rest (nickname lookups, chat limits, seat reservations) only
+/// show when the block executes.
 struct MutationRoot;
 
 #[Object]
 impl MutationRoot {
-    /// Join the match with a nickname
-    async fn join_match(&self, ctx: &Context<'_>, nickname: String) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::JoinMatch { nickname });
-        true
+    /// Join the match (with a nickname, unless you registered a profile),
+    /// optionally authorizing a session key for the new seat
+    async fn join_match(
+        &self,
+        ctx: &Context<'_>,
+        nickname: Option<String>,
+        invite_code: Option<String>,
+        session: Option<SessionGrant>,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::JoinMatch {
+            nickname,
+            invite_code,
+            session,
+            expected_version,
+        })
+    }
+
+    /// Fill an open seat with a bot opponent (host only)
+    async fn add_bot(
+        &self,
+        ctx: &Context<'_>,
+        difficulty: BotDifficulty,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::AddBot {
+            difficulty,
+            expected_version,
+        })
+    }
+
+    /// Mark yourself away; your seat is held for the grace period
+    async fn step_away(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::StepAway { expected_version })
+    }
+
+    /// Mark the idle turn holder away
+    async fn mark_away(
+        &self,
+        ctx: &Context<'_>,
+        player_index: usize,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::MarkAway {
+            player_index,
+            expected_version,
+        })
+    }
+
+    /// Come back to your seat after being away
+    async fn rejoin(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::Rejoin { expected_version })
+    }
+
+    /// Forfeit an away seat whose grace period has ended
+    async fn forfeit_away(
+        &self,
+        ctx: &Context<'_>,
+        player_index: usize,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::ForfeitAway {
+            player_index,
+            expected_version,
+        })
+    }
+
+    /// Ask to pause the match (it pauses once every active player agrees)
+    async fn request_pause(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::RequestPause { expected_version })
+    }
+
+    /// Agree to a pending pause request
+    async fn accept_pause(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::AcceptPause { expected_version })
+    }
+
+    /// Agree to resume the paused match (it resumes once every active player agrees)
+    async fn resume(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::Resume { expected_version })
+    }
+
+    /// Ask to undo the move you just made (unranked matches only)
+    async fn request_takeback(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::RequestTakeback { expected_version })
+    }
+
+    /// Agree to the pending takeback
+    async fn accept_takeback(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::AcceptTakeback { expected_version })
+    }
+
+    /// Refuse the pending takeback (or withdraw your own)
+    async fn decline_takeback(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::DeclineTakeback { expected_version })
+    }
+
+    /// Authorize a session key to act for your seat (replaces any previous one)
+    async fn authorize_session(
+        &self,
+        ctx: &Context<'_>,
+        key: AccountOwner,
+        lifetime_secs: u64,
+        scope: SessionScope,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::AuthorizeSession {
+            session: SessionGrant {
+                key,
+                lifetime_secs,
+                scope,
+            },
+            expected_version,
+        })
+    }
+
+    /// Revoke your seat's session key
+    async fn revoke_session(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::RevokeSession { expected_version })
+    }
+
+    /// Register (or change) your persistent name at this table
+    async fn register_profile(&self, ctx: &Context<'_>, nickname: String, player: Option<AccountOwner>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::RegisterProfile { nickname })
     }
 
-    /// Start the match (host only)
-    async fn start_match(&self, ctx: &Context<'_>) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::StartMatch);
-        true
+    /// Post a short text message or an emote to the match chat
+    async fn send_chat(&self, ctx: &Context<'_>, text: Option<String>, emote: Option<Emote>, player: Option<AccountOwner>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::SendChat { text, emote })
+    }
+
+    /// Mark yourself ready (or not) in the lobby
+    async fn set_ready(&self, ctx: &Context<'_>, ready: bool, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::SetReady {
+            ready,
+            expected_version,
+        })
+    }
+
+    /// Cancel a table left waiting past its lobby expiry
+    async fn expire_lobby(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::ExpireLobby { expected_version })
+    }
+
+    /// Start the match (once everyone else is ready)
+    async fn start_match(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::StartMatch { expected_version })
     }
 
     /// Play a card from your hand
+    ///
+    /// Like every move, this is checked against the current state first and
+    /// only scheduled if the contract would accept it. Pass `player` (your
+    /// account or session key) so the checks can confirm it is your turn;
+    /// without it the current turn holder is assumed and the outcome says
+    /// `turnVerified: false`.
     async fn play_card(
         &self,
         ctx: &Context<'_>,
-        card_index: i32,
-        chosen_suit: Option<String>,
-    ) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        
-        // Convert String to CardSuit if provided
-        let suit = chosen_suit.and_then(|s| match s.to_lowercase().as_str() {
-            "circle" => Some(CardSuit::Circle),
-            "cross" => Some(CardSuit::Cross),
-            "triangle" => Some(CardSuit::Triangle),
-            "square" => Some(CardSuit::Square),
-            _ => None,
-        });
-
-        runtime.schedule_operation(&Operation::PlayCard {
-            card_index: card_index as usize,
-            chosen_suit: suit,
-        });
-        true
+        card_id: u32,
+        chosen_suit: Option<CardSuit>,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::PlayCard {
+            card_id,
+            chosen_suit,
+            expected_version,
+        })
+    }
+
+    /// Choose the demanded suit after playing a Whot card without one
+    async fn choose_suit(
+        &self,
+        ctx: &Context<'_>,
+        suit: CardSuit,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::ChooseSuit {
+            suit,
+            expected_version,
+        })
     }
 
     /// Draw a card from the deck
-    async fn draw_card(&self, ctx: &Context<'_>) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::DrawCard);
-        true
+    async fn draw_card(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::DrawCard { expected_version })
     }
 
     /// Call "Last Card" when you have one card remaining
-    async fn call_last_card(&self, ctx: &Context<'_>) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::CallLastCard);
-        true
+    async fn call_last_card(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::CallLastCard { expected_version })
+    }
+
+    /// Set standing orders applied automatically when it is your turn
+    async fn set_auto_actions(
+        &self,
+        ctx: &Context<'_>,
+        auto_draw: bool,
+        auto_defend: bool,
+        auto_call_last_card: bool,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::SetAutoActions {
+            auto_draw,
+            auto_defend,
+            auto_call_last_card,
+            expected_version,
+        })
     }
 
     /// Challenge another player for not calling "Last Card"
-    async fn challenge_last_card(&self, ctx: &Context<'_>, player_index: i32) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::ChallengeLastCard {
-            player_index: player_index as usize,
-        });
-        true
+    async fn challenge_last_card(
+        &self,
+        ctx: &Context<'_>,
+        player_index: usize,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::ChallengeLastCard {
+            player_index,
+            expected_version,
+        })
     }
 
     /// Leave the match
-    async fn leave_match(&self, ctx: &Context<'_>) -> bool {
-        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<LinotService>>>();
-        runtime.schedule_operation(&Operation::LeaveMatch);
-        true
+    async fn leave_match(&self, ctx: &Context<'_>, player: Option<AccountOwner>, expected_version: Option<u64>) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::LeaveMatch { expected_version })
+    }
+
+    /// Set up a new match on this table (host only, once the table is free)
+    async fn create_match(
+        &self,
+        ctx: &Context<'_>,
+        config: MatchConfig,
+        player: Option<AccountOwner>,
+        expected_version: Option<u64>,
+    ) -> MoveOutcome {
+        schedule_checked(ctx, player, Operation::CreateMatch {
+            config,
+            expected_version,
+        })
     }
 }
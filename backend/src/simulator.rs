@@ -0,0 +1,282 @@
+//! Offline bot tournament simulator
+//!
+//! Plays seeded matches between strategies using the same `GameEngine`
+//! transitions as the contract, and reports win rates, game length and the
+//! impact of each special card.
+//!
+//! ```text
+//! cargo run --bin linot_simulator -- --games 5000 --seed club --strategies tactician,shed-highest
+//! ```
+
+mod bot;
+#[allow(dead_code)] // Shared with the contract; the simulator only drives the turn transitions
+mod game_engine;
+#[allow(dead_code)] // Shared with the contract; the simulator keeps matches in memory
+mod state;
+
+use std::collections::BTreeMap;
+
+use linera_sdk::linera_base_types::AccountOwner;
+
+use crate::bot::{BotMove, SeatView, Strategy};
+use crate::game_engine::{GameEngine, GameResult, SpecialEffect};
+use crate::state::{MatchData, MatchEventKind, MatchStatus, Player};
+
+/// Moves after which a match is abandoned as unfinished
+const MAX_MOVES: usize = 2_000;
+
+/// Special effects reported on, with their display names
+const SPECIALS: [(SpecialEffect, &str); 6] = [
+    (SpecialEffect::ChooseShape, "Whot"),
+    (SpecialEffect::PlayAgain, "Hold On"),
+    (SpecialEffect::DrawTwo, "Pick Two"),
+    (SpecialEffect::DrawThree, "Pick Three"),
+    (SpecialEffect::SkipNext, "Suspension"),
+    (SpecialEffect::AllDrawOne, "General Market"),
+];
+
+struct Options {
+    games: usize,
+    seed: String,
+    lineup: Vec<&'static dyn Strategy>,
+}
+
+/// Outcome of one simulated match
+struct MatchRecord {
+    /// Strategy index (into the lineup) of each seat
+    seats: Vec<usize>,
+    /// Seats that won outright, or shared a draw
+    winners: Vec<usize>,
+    /// Whether the match was a draw
+    drawn: bool,
+    /// Number of moves played, `None` if abandoned
+    moves: Option<usize>,
+    /// Special effects played by each seat
+    specials: Vec<Vec<SpecialEffect>>,
+}
+
+fn main() {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!(
+                "usage: linot_simulator [--games N] [--seed TEXT] [--strategies a,b,...]\n\
+                 strategies: {}",
+                strategy_names().join(", ")
+            );
+            std::process::exit(2);
+        }
+    };
+
+    let records: Vec<MatchRecord> = (0..options.games)
+        .map(|game| play_match(&options, game))
+        .collect();
+
+    report(&options, &records);
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        games: 1_000,
+        seed: "linot".to_string(),
+        lineup: vec![&bot::FirstLegal, &bot::ShedHighest],
+    };
+
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--games" => {
+                options.games = value
+                    .parse()
+                    .map_err(|_| format!("invalid game count: {}", value))?;
+            }
+            "--seed" => options.seed = value,
+            "--strategies" => {
+                options.lineup = value
+                    .split(',')
+                    .map(|name| {
+                        bot::reference_strategies()
+                            .into_iter()
+                            .find(|strategy| strategy.name() == name.trim())
+                            .ok_or_else(|| format!("unknown strategy: {}", name))
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            _ => return Err(format!("unknown option: {}", flag)),
+        }
+    }
+
+    if !(2..=6).contains(&options.lineup.len()) {
+        return Err("a table needs 2 to 6 strategies".to_string());
+    }
+    Ok(options)
+}
+
+fn strategy_names() -> Vec<&'static str> {
+    bot::reference_strategies()
+        .iter()
+        .map(|strategy| strategy.name())
+        .collect()
+}
+
+/// Play one seeded match, rotating seats so no strategy always moves first
+fn play_match(options: &Options, game: usize) -> MatchRecord {
+    let table_size = options.lineup.len();
+    let seats: Vec<usize> = (0..table_size).map(|seat| (seat + game) % table_size).collect();
+
+    let mut state = MatchData::default();
+    for (seat, &strategy) in seats.iter().enumerate() {
+        let mut address = [0u8; 20];
+        address[19] = seat as u8;
+        state.players.push(Player::new(
+            AccountOwner::Address20(address),
+            options.lineup[strategy].name().to_string(),
+        ));
+    }
+
+    let seed = format!("{}-{}", options.seed, game);
+    let mut events = Vec::new();
+    GameEngine::start_match(&mut state, seed.as_bytes(), &mut events);
+
+    let mut moves = 0;
+    while state.status == MatchStatus::InProgress && moves < MAX_MOVES {
+        let seat = state.current_player_index;
+        let strategy = options.lineup[seats[seat]];
+        let chosen = SeatView::new(&state, seat)
+            .map(|view| bot::decide(strategy, &view))
+            .unwrap_or(BotMove::Draw);
+
+        let outcome = match chosen {
+            BotMove::Play { card_id, chosen_suit } => {
+                GameEngine::play_card(&mut state, card_id, chosen_suit, &mut events)
+            }
+            BotMove::Draw => GameEngine::draw_card(&mut state, seed.as_bytes(), &mut events),
+        };
+        if let Err(error) = outcome {
+            panic!("{} made an illegal move: {}", strategy.name(), error);
+        }
+        moves += 1;
+    }
+
+    let mut specials = vec![Vec::new(); table_size];
+    for event in &events {
+        if event.kind != MatchEventKind::CardPlayed {
+            continue;
+        }
+        let (Some(seat), Some(&card_id)) = (event.player_index, event.card_ids.first()) else {
+            continue;
+        };
+        let card = &GameEngine::create_deck()[card_id as usize];
+        let effect = GameEngine::get_card_effect(card);
+        if effect != SpecialEffect::None {
+            specials[seat].push(effect);
+        }
+    }
+
+    let finished = state.status == MatchStatus::Finished;
+    MatchRecord {
+        seats,
+        winners: match GameEngine::final_result(&state) {
+            Some(GameResult::Winner(winner)) => vec![winner],
+            Some(GameResult::TeamWinner { players, .. }) => players,
+            Some(GameResult::Placements(order)) => order.into_iter().take(1).collect(),
+            Some(GameResult::Draw(tied)) => tied,
+            None => Vec::new(),
+        },
+        drawn: matches!(GameEngine::final_result(&state), Some(GameResult::Draw(_))),
+        moves: finished.then_some(moves),
+        specials,
+    }
+}
+
+fn report(options: &Options, records: &[MatchRecord]) {
+    let names: Vec<&str> = options.lineup.iter().map(|strategy| strategy.name()).collect();
+    println!(
+        "{} games, seed \"{}\", table: {}",
+        records.len(),
+        options.seed,
+        names.join(" vs ")
+    );
+
+    // Win rates per lineup entry
+    println!();
+    println!("{:<16} {:>8} {:>8} {:>8}", "strategy", "wins", "draws", "win %");
+    for (index, name) in names.iter().enumerate() {
+        let mut wins = 0;
+        let mut draws = 0;
+        for record in records {
+            for &seat in &record.winners {
+                if record.seats[seat] == index {
+                    if record.drawn {
+                        draws += 1;
+                    } else {
+                        wins += 1;
+                    }
+                }
+            }
+        }
+        println!(
+            "{:<16} {:>8} {:>8} {:>7.1}%",
+            name,
+            wins,
+            draws,
+            percent(wins, records.len())
+        );
+    }
+
+    // Game length
+    let lengths: Vec<usize> = records.iter().filter_map(|record| record.moves).collect();
+    println!();
+    if lengths.is_empty() {
+        println!("no game finished within {} moves", MAX_MOVES);
+    } else {
+        println!(
+            "average length: {:.1} moves ({} finished, {} abandoned after {} moves)",
+            lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+            lengths.len(),
+            records.len() - lengths.len(),
+            MAX_MOVES
+        );
+    }
+
+    // Special-card impact: win rate of seats that played the card vs seats that did not
+    println!();
+    println!(
+        "{:<20} {:>10} {:>14} {:>14}",
+        "special card", "plays/game", "win % played", "win % without"
+    );
+    for (effect, label) in SPECIALS {
+        let mut plays = 0;
+        let mut with: BTreeMap<bool, (usize, usize)> = BTreeMap::new();
+        for record in records {
+            for (seat, played) in record.specials.iter().enumerate() {
+                let count = played.iter().filter(|&&e| e == effect).count();
+                plays += count;
+                let won = !record.drawn && record.winners.contains(&seat);
+                let entry = with.entry(count > 0).or_default();
+                entry.0 += usize::from(won);
+                entry.1 += 1;
+            }
+        }
+        let (won_with, seen_with) = with.get(&true).copied().unwrap_or_default();
+        let (won_without, seen_without) = with.get(&false).copied().unwrap_or_default();
+        println!(
+            "{:<20} {:>10.2} {:>13.1}% {:>13.1}%",
+            label,
+            plays as f64 / records.len().max(1) as f64,
+            percent(won_with, seen_with),
+            percent(won_without, seen_without)
+        );
+    }
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        100.0 * part as f64 / whole as f64
+    }
+}
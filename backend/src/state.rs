@@ -1,10 +1,11 @@
 use linera_sdk::{
     linera_base_types::AccountOwner,
-    views::{RegisterView, RootView, ViewStorageContext},
+    views::{LogView, MapView, RegisterView, RootView, ViewStorageContext},
 };
 use serde::{Deserialize, Serialize};
 
-use linot::{Card, CardSuit};
+use linot::{BotDifficulty, Card, CardSuit, Emote, SessionScope};
+pub use linot::{MatchConfig, MatchStatus};
 
 /// Root application state stored on-chain using Linera Views
 #[derive(RootView)]
@@ -16,31 +17,18 @@ pub struct LinotState {
     pub match_data: RegisterView<MatchData>,
     /// Optional betting pool for staking (Wave 4-5)
     pub betting_pool: RegisterView<Option<BettingPool>>,
-}
-
-// ============ Match Configuration ============
-
-#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
-pub struct MatchConfig {
-    /// Maximum players allowed (2 for V1)
-    pub max_players: u8,
-    /// Host account who created the match
-    pub host: Option<AccountOwner>,
-    /// Whether this is a ranked/competitive match
-    pub is_ranked: bool,
-    /// Strict mode: must draw if no valid move
-    pub strict_mode: bool,
-}
-
-impl Default for MatchConfig {
-    fn default() -> Self {
-        Self {
-            max_players: 2,
-            host: None,
-            is_ranked: false,
-            strict_mode: false,
-        }
-    }
+    /// Append-only log of match events, referencing cards by ID
+    pub events: LogView<MatchEvent>,
+    /// Match state before the last move, kept for a takeback (unranked only)
+    pub takeback: RegisterView<Option<TakebackPoint>>,
+    /// Append-only chat log of the table
+    pub chat: LogView<ChatMessage>,
+    /// Recent chat activity per author, for rate limiting
+    pub chat_allowance: MapView<AccountOwner, ChatAllowance>,
+    /// Registered names, used automatically when their owner joins
+    pub profiles: MapView<AccountOwner, Profile>,
+    /// Owner of each registered name, keyed by `linot::nickname_key`
+    pub profile_names: MapView<String, AccountOwner>,
 }
 
 // ============ Match Data ============
@@ -59,14 +47,48 @@ pub struct MatchData {
     pub status: MatchStatus,
     /// Index of winning player (if finished)
     pub winner_index: Option<usize>,
+    /// Players sharing a drawn result (if finished without a winner)
+    pub tied_players: Vec<usize>,
+    /// Winning team (if a partnership match finished with a winner)
+    pub winning_team: Option<u8>,
+    /// Seats in finishing order, 1st first (play-out matches)
+    pub placements: Vec<usize>,
+    /// Seats that left after the match started, first leaver first
+    pub left_order: Vec<usize>,
+    /// Point total of each hand when the match finished, by seat
+    pub final_tally: Vec<u32>,
     /// Round number (for reshuffle entropy)
     pub round_number: u32,
+    /// Matches set up on this table before this one (for shuffle entropy)
+    pub match_number: u32,
     /// Timestamp when match was created
     pub created_at: u64,
     /// Active shape demand (set by Whot card)
     pub active_shape_demand: Option<CardSuit>,
     /// Pending penalty cards to draw (Pick Two/Three)
     pub pending_penalty: u8,
+    /// Seat that laid the pending penalty
+    pub penalty_source: Option<usize>,
+    /// What the current player is expected to do next
+    pub turn_phase: TurnPhase,
+    /// Partnership mode, copied from `MatchConfig` when the match starts
+    pub team_mode: bool,
+    /// Partner defence variant, copied from `MatchConfig` when the match starts
+    pub partner_can_defend: bool,
+    /// Play-out mode, copied from `MatchConfig` when the match starts
+    pub play_out: bool,
+    /// When the current player's turn began (microseconds)
+    pub turn_started_at: u64,
+    /// Seats agreeing to the pending pause (or, while paused, to resuming)
+    pub pause_votes: Vec<usize>,
+    /// When the match was paused (microseconds), while it is
+    pub paused_at: Option<u64>,
+    /// Seat asking to take back its last move, if any
+    pub takeback_request: Option<usize>,
+    /// Opponents agreeing to the pending takeback
+    pub takeback_votes: Vec<usize>,
+    /// State version, incremented by every successful operation
+    pub version: u64,
 }
 
 impl Default for MatchData {
@@ -78,10 +100,27 @@ impl Default for MatchData {
             discard_pile: Vec::new(),
             status: MatchStatus::Waiting,
             winner_index: None,
+            tied_players: Vec::new(),
+            winning_team: None,
+            placements: Vec::new(),
+            left_order: Vec::new(),
+            final_tally: Vec::new(),
             round_number: 0,
+            match_number: 0,
             created_at: 0,
             active_shape_demand: None,
             pending_penalty: 0,
+            penalty_source: None,
+            turn_phase: TurnPhase::Play,
+            team_mode: false,
+            partner_can_defend: true,
+            play_out: false,
+            turn_started_at: 0,
+            pause_votes: Vec::new(),
+            paused_at: None,
+            takeback_request: None,
+            takeback_votes: Vec::new(),
+            version: 0,
         }
     }
 }
@@ -102,6 +141,16 @@ pub struct Player {
     pub card_count: usize,
     /// Whether player called "Last Card!"
     pub called_last_card: bool,
+    /// Difficulty if this seat is played by the contract's bot
+    pub bot: Option<BotDifficulty>,
+    /// Standing orders applied when the turn reaches this player
+    pub auto_actions: AutoActions,
+    /// Confirmed present in the lobby (bots always are)
+    pub ready: bool,
+    /// When the player was marked away (microseconds), if they are
+    pub away_since: Option<u64>,
+    /// Session key acting for this seat, if one is authorized
+    pub session: Option<SessionKey>,
 }
 
 impl Player {
@@ -114,6 +163,28 @@ impl Player {
             is_active: true,
             card_count: 0,
             called_last_card: false,
+            bot: None,
+            auto_actions: AutoActions::default(),
+            ready: false,
+            away_since: None,
+            session: None,
+        }
+    }
+
+    /// Create the bot numbered `number` at its table
+    ///
+    /// Bots get a synthetic owner that no signer can control.
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn new_bot(number: usize, difficulty: BotDifficulty) -> Self {
+        let mut address = [0u8; 20];
+        address[..9].copy_from_slice(b"linot-bot");
+        address[19] = number as u8;
+        let nickname = format!("{:?} Bot {}", difficulty, number + 1);
+
+        Self {
+            bot: Some(difficulty),
+            ready: true,
+            ..Self::new(AccountOwner::Address20(address), nickname)
         }
     }
 
@@ -124,16 +195,213 @@ impl Player {
     }
 }
 
-// ============ Match Status ============
+// ============ Session Keys ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct SessionKey {
+    /// Account that signs for the seat
+    pub key: AccountOwner,
+    /// When the key stops working (microseconds)
+    pub expires_at: u64,
+    /// What the key may do
+    pub scope: SessionScope,
+}
+
+// ============ Standing Orders ============
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct AutoActions {
+    /// Draw automatically when holding no legal card
+    pub auto_draw: bool,
+    /// Answer Pick Two/Three with a matching card automatically
+    pub auto_defend: bool,
+    /// Call "Last Card!" automatically when down to one card
+    pub auto_call_last_card: bool,
+}
+
+impl Default for AutoActions {
+    fn default() -> Self {
+        Self {
+            auto_draw: false,
+            auto_defend: false,
+            // Matches the automatic Last Card call of V1
+            auto_call_last_card: true,
+        }
+    }
+}
+
+// ============ Turn Phase ============
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
+pub enum TurnPhase {
+    /// Current player plays or draws a card
+    Play,
+    /// Current player played a Whot card and must choose the demanded suit
+    ChooseSuit,
+}
+
+// ============ Match Events ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct MatchEvent {
+    /// State version produced by the operation that caused this event
+    pub version: u64,
+    /// Seat that acted or was affected, if any
+    pub player_index: Option<usize>,
+    /// What happened
+    pub kind: MatchEventKind,
+    /// IDs of the cards involved
+    pub card_ids: Vec<u32>,
+    /// Suit chosen with a Whot card
+    pub suit: Option<CardSuit>,
+    /// Cards owed as a penalty (`PenaltyApplied`)
+    pub penalty_cards: u8,
+}
+
+impl MatchEvent {
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn new(version: u64, player_index: Option<usize>, kind: MatchEventKind) -> Self {
+        Self {
+            version,
+            player_index,
+            kind,
+            card_ids: Vec::new(),
+            suit: None,
+            penalty_cards: 0,
+        }
+    }
+
+    /// Attach the IDs of the cards involved
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn with_cards(mut self, cards: &[Card]) -> Self {
+        self.card_ids = cards.iter().map(|card| card.id).collect();
+        self
+    }
+
+    /// Attach the suit chosen with a Whot card
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn with_suit(mut self, suit: CardSuit) -> Self {
+        self.suit = Some(suit);
+        self
+    }
+
+    /// Attach the number of cards owed as a penalty
+    #[allow(dead_code)] // Used in contract.rs
+    pub fn with_penalty(mut self, cards: u8) -> Self {
+        self.penalty_cards = cards;
+        self
+    }
+
+    /// Whether the card IDs are private to the affected player
+    #[allow(dead_code)] // Used in service.rs
+    pub fn is_private(&self) -> bool {
+        matches!(self.kind, MatchEventKind::CardsDealt | MatchEventKind::CardsDrawn)
+    }
+}
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
-pub enum MatchStatus {
-    /// Waiting for players to join
-    Waiting,
-    /// Match is in progress
-    InProgress,
-    /// Match has finished
-    Finished,
+pub enum MatchEventKind {
+    /// A new match was set up on the table; earlier events belong to the last one
+    MatchCreated,
+    /// A player took a seat
+    PlayerJoined,
+    /// A player marked themselves ready or not ready in the lobby
+    ReadyChanged,
+    /// The lobby expired, or everyone left, and the table was cancelled
+    MatchCancelled,
+    /// The match started; the card is the first discard
+    MatchStarted,
+    /// Initial hand dealt to a player
+    CardsDealt,
+    /// A player played a card
+    CardPlayed,
+    /// A Whot player chose the demanded suit
+    SuitChosen,
+    /// A player drew cards (normal draw, penalty or General Market)
+    CardsDrawn,
+    /// A player had to pay a penalty (Pick Two/Three, or a missed Last Card
+    /// call); the cards themselves follow as `CardsDrawn`
+    PenaltyApplied,
+    /// The turn passed to another seat (logged once per operation)
+    TurnChanged,
+    /// The discard pile (minus its top card) was shuffled back into the deck
+    DeckReshuffled,
+    /// A player called "Last Card!"
+    LastCardCalled,
+    /// A player left the match (or their away seat was forfeited)
+    PlayerLeft,
+    /// A player stepped away or was marked away; their seat is held
+    PlayerAway,
+    /// An away player came back to their seat
+    PlayerRejoined,
+    /// A player asked for (or agreed to) a pause
+    PauseRequested,
+    /// Every active player agreed and the match paused
+    MatchPaused,
+    /// A player agreed to resume the paused match
+    ResumeRequested,
+    /// Every active player agreed and the match resumed
+    MatchResumed,
+    /// A player asked to take back their last move
+    TakebackRequested,
+    /// An opponent agreed to the pending takeback
+    TakebackAccepted,
+    /// An opponent refused the pending takeback
+    TakebackDeclined,
+    /// The last move was undone; the match is back to the state before it
+    TakebackApplied,
+    /// A player emptied their hand and took the next place (play-out matches)
+    PlayerFinished,
+    /// The match finished
+    MatchFinished,
+}
+
+// ============ Takebacks ============
+
+/// Snapshot taken before a move in an unranked match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakebackPoint {
+    /// Seat that made the move
+    pub seat: usize,
+    /// Match state just before the move
+    pub before: MatchData,
+}
+
+// ============ Profiles ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct Profile {
+    /// Owner account
+    pub owner: AccountOwner,
+    /// Registered nickname
+    pub nickname: String,
+    /// When the name was (last) registered (microseconds)
+    pub registered_at: u64,
+}
+
+// ============ Chat ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ChatMessage {
+    /// Account that posted the message
+    pub author: AccountOwner,
+    /// Author's seat, or None for a spectator
+    pub seat: Option<usize>,
+    /// Text of the message, if it is not an emote
+    pub text: Option<String>,
+    /// Emote sent instead of text
+    pub emote: Option<Emote>,
+    /// When the message was posted (microseconds)
+    pub sent_at: u64,
+}
+
+/// Messages an author posted in the current rate-limit window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatAllowance {
+    /// Start of the window (microseconds)
+    pub window_start: u64,
+    /// Messages posted since then
+    pub count: u32,
 }
 
 // ============ Betting Pool (Wave 4-5) ============
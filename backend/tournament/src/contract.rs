@@ -0,0 +1,417 @@
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+mod pairing;
+mod state;
+
+use linera_sdk::{
+    abi::WithContractAbi,
+    linera_base_types::{AccountOwner, ApplicationId, StreamName, StreamUpdate},
+    views::{RootView, View},
+    Contract, ContractRuntime,
+};
+
+use crate::state::{Entrant, Table, TournamentState, TournamentStatus};
+use linot::{
+    LinotAbi, LinotResponse, MatchConfig, MatchStatus, Operation as LinotOperation, SeatReservation,
+    RESULTS_STREAM,
+};
+use linot_tournament::{
+    Operation, TournamentAbi, TournamentConfig, TournamentError, TournamentFormat,
+    TournamentParameters,
+};
+
+pub struct TournamentContract {
+    state: TournamentState,
+    runtime: ContractRuntime<Self>,
+}
+
+linera_sdk::contract!(TournamentContract);
+
+impl WithContractAbi for TournamentContract {
+    type Abi = TournamentAbi;
+}
+
+impl Contract for TournamentContract {
+    type Message = ();
+    type Parameters = TournamentParameters;
+    type InstantiationArgument = TournamentConfig;
+    type EventValue = ();
+
+    async fn load(runtime: ContractRuntime<Self>) -> Self {
+        let state = TournamentState::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load state");
+        TournamentContract { state, runtime }
+    }
+
+    async fn instantiate(&mut self, mut config: Self::InstantiationArgument) {
+        if !(2..=pairing::MAX_TABLE_SIZE as u8).contains(&config.table_size) {
+            panic!("{}", TournamentError::InvalidTableSize(config.table_size));
+        }
+
+        // Default the organizer to the creator
+        if config.organizer.is_none() {
+            config.organizer = self.runtime.authenticated_signer();
+        }
+
+        self.state.config.set(config);
+        self.state.status.set(TournamentStatus::Registration);
+    }
+
+    async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
+        let result = match operation {
+            Operation::Register { nickname } => self.handle_register(nickname),
+            Operation::Withdraw => self.handle_withdraw(),
+            Operation::StartTournament => self.handle_start(),
+            Operation::CollectResults => self.handle_collect_results(),
+        };
+
+        if let Err(e) = result {
+            panic!("Operation failed: {}", e);
+        }
+    }
+
+    async fn execute_message(&mut self, _message: Self::Message) {
+        // Tables live on this chain, so there are no cross-chain messages
+    }
+
+    async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
+        if *self.state.status.get() != TournamentStatus::InProgress {
+            return;
+        }
+
+        // A table announced its result: score it, and advance once the round is done
+        let round = *self.state.round.get();
+        let announced = updates.iter().any(|update| {
+            update.stream_id.stream_name == StreamName(RESULTS_STREAM.to_vec())
+                && self.state.tables.get().iter().any(|table| {
+                    table.round == round
+                        && update.stream_id.application_id.user_application_id() == Some(&table.application_id)
+                })
+        });
+        if announced {
+            self.collect_results();
+        }
+    }
+
+    async fn store(mut self) {
+        self.state.save().await.expect("Failed to save state");
+    }
+}
+
+impl TournamentContract {
+    fn caller(&mut self) -> Result<AccountOwner, TournamentError> {
+        self.runtime
+            .authenticated_signer()
+            .ok_or(TournamentError::CallerRequired)
+    }
+
+    /// Handle an entrant registering
+    fn handle_register(&mut self, nickname: String) -> Result<(), TournamentError> {
+        let caller = self.caller()?;
+        let config = self.state.config.get().clone();
+        let mut entrants = self.state.entrants.get().clone();
+        let nickname = linot::validate_nickname(&nickname)?;
+
+        // Validate: registration is open
+        if *self.state.status.get() != TournamentStatus::Registration {
+            return Err(TournamentError::RegistrationClosed);
+        }
+
+        // Validate: not already registered
+        if entrants.iter().any(|e| e.owner == caller) {
+            return Err(TournamentError::AlreadyRegistered);
+        }
+
+        // Validate: space left
+        if entrants.len() as u32 >= config.max_entrants {
+            return Err(TournamentError::TournamentFull(config.max_entrants));
+        }
+
+        entrants.push(Entrant {
+            owner: caller,
+            nickname,
+            seed: entrants.len() as u32 + 1,
+            points: 0,
+            eliminated: false,
+        });
+        self.state.entrants.set(entrants);
+
+        Ok(())
+    }
+
+    /// Handle an entrant withdrawing before the start
+    fn handle_withdraw(&mut self) -> Result<(), TournamentError> {
+        let caller = self.caller()?;
+        let mut entrants = self.state.entrants.get().clone();
+
+        if *self.state.status.get() != TournamentStatus::Registration {
+            return Err(TournamentError::RegistrationClosed);
+        }
+
+        let position = entrants
+            .iter()
+            .position(|e| e.owner == caller)
+            .ok_or(TournamentError::NotRegistered)?;
+        entrants.remove(position);
+
+        // Close the gap in the seeding
+        for (i, entrant) in entrants.iter_mut().enumerate() {
+            entrant.seed = i as u32 + 1;
+        }
+        self.state.entrants.set(entrants);
+
+        Ok(())
+    }
+
+    /// Handle the organizer closing registration and opening round one
+    fn handle_start(&mut self) -> Result<(), TournamentError> {
+        let caller = self.caller()?;
+        let config = self.state.config.get().clone();
+        let entrants = self.state.entrants.get().clone();
+
+        // Validate: caller is the organizer
+        if config.organizer != Some(caller) {
+            return Err(TournamentError::OnlyOrganizerCanStart);
+        }
+
+        // Validate: registration is still open
+        if *self.state.status.get() != TournamentStatus::Registration {
+            return Err(TournamentError::RegistrationClosed);
+        }
+
+        // Validate: enough entrants for one table
+        if entrants.len() < 2 {
+            return Err(TournamentError::NotEnoughEntrants(2));
+        }
+
+        self.state.status.set(TournamentStatus::InProgress);
+        let seeded = entrants.iter().map(|e| e.owner).collect();
+        self.open_round(seeded);
+
+        Ok(())
+    }
+
+    /// Handle reading finished tables, scoring them and advancing the round
+    ///
+    /// Finished matches are picked up from their result stream, so this is
+    /// only needed for tables that never announce one: a lobby that expired
+    /// or a match cancelled after everyone left. Anyone may call it; it is
+    /// idempotent and only advances once every table of the current round
+    /// has a result.
+    fn handle_collect_results(&mut self) -> Result<(), TournamentError> {
+        if *self.state.status.get() != TournamentStatus::InProgress {
+            return Err(TournamentError::TournamentNotInProgress);
+        }
+
+        self.collect_results();
+
+        Ok(())
+    }
+
+    /// Score every table of the current round that has a result, and start
+    /// the next round once they all do
+    fn collect_results(&mut self) {
+        let round = *self.state.round.get();
+        let mut tables = self.state.tables.get().clone();
+        let mut entrants = self.state.entrants.get().clone();
+
+        for table in tables.iter_mut() {
+            if table.round != round || table.result.is_some() {
+                continue;
+            }
+            let Some(order) = self.read_table_result(table) else {
+                continue;
+            };
+
+            // Score placements
+            for (place, owner) in order.iter().enumerate() {
+                if let Some(entrant) = entrants.iter_mut().find(|e| e.owner == *owner) {
+                    entrant.points += pairing::placement_points(place, order.len());
+                }
+            }
+            table.result = Some(order);
+
+            let results = StreamName(RESULTS_STREAM.to_vec());
+            let chain_id = self.runtime.chain_id();
+            self.runtime.unsubscribe_from_events(chain_id, table.application_id, results);
+        }
+
+        let round_done = tables
+            .iter()
+            .filter(|table| table.round == round)
+            .all(|table| table.result.is_some());
+
+        self.state.tables.set(tables);
+        self.state.entrants.set(entrants);
+
+        if round_done {
+            self.advance_round();
+        }
+    }
+
+    /// Read a table's finishing order from its Linot application, if the match finished
+    ///
+    /// A table whose lobby expired before everyone sat down is cancelled (if
+    /// nobody has yet) and scored as a forfeit: the entrants who took their
+    /// seat place ahead of the no-shows, in seat order. A match cancelled
+    /// after it started places anyone who went out first, then the leavers
+    /// with the last to leave ahead. Entrants the table never saw are always
+    /// placed last, in seat order; anyone else who sat at the table is ignored.
+    fn read_table_result(&mut self, table: &Table) -> Option<Vec<AccountOwner>> {
+        let application_id = table.application_id.with_abi::<LinotAbi>();
+        let placements = match self
+            .runtime
+            .call_application(false, application_id, &LinotOperation::GetResult)
+        {
+            LinotResponse::Result(Some(result)) => result.placements,
+            _ => self.read_forfeit(table)?,
+        };
+
+        let mut order: Vec<AccountOwner> = placements
+            .into_iter()
+            .filter(|owner| table.seats.contains(owner))
+            .collect();
+        let missing: Vec<AccountOwner> = table
+            .seats
+            .iter()
+            .filter(|owner| !order.contains(owner))
+            .copied()
+            .collect();
+        order.extend(missing);
+
+        Some(order)
+    }
+
+    /// Forfeit order of a cancelled table, or of one whose lobby has expired
+    fn read_forfeit(&mut self, table: &Table) -> Option<Vec<AccountOwner>> {
+        let application_id = table.application_id.with_abi::<LinotAbi>();
+        let LinotResponse::PublicState(public) =
+            self.runtime
+                .call_application(false, application_id, &LinotOperation::GetPublicState)
+        else {
+            return None;
+        };
+
+        let now = self.runtime.system_time().micros();
+        match public.status {
+            MatchStatus::Waiting if public.lobby_expires_at.is_some_and(|deadline| now >= deadline) => {
+                let expire = LinotOperation::ExpireLobby { expected_version: None };
+                self.runtime.call_application(false, application_id, &expire);
+            }
+            MatchStatus::Cancelled if !public.left_order.is_empty() => {
+                let mut order = public.placements.clone();
+                order.extend(public.left_order.iter().rev());
+                return Some(order);
+            }
+            MatchStatus::Cancelled => {}
+            _ => return None,
+        }
+
+        // Never started: everyone who sat down forfeits ahead of the no-shows
+        Some(public.players.iter().map(|seat| seat.owner).collect())
+    }
+
+    /// Start the next round, or finish the tournament
+    fn advance_round(&mut self) {
+        let config = self.state.config.get().clone();
+        let round = *self.state.round.get();
+        let mut entrants = self.state.entrants.get().clone();
+
+        match config.format {
+            TournamentFormat::SingleElimination => {
+                // Table winners advance; everyone else is knocked out
+                let winners: Vec<AccountOwner> = self
+                    .state
+                    .tables
+                    .get()
+                    .iter()
+                    .filter(|table| table.round == round)
+                    .filter_map(|table| table.result.as_ref()?.first().copied())
+                    .collect();
+                for entrant in entrants.iter_mut() {
+                    entrant.eliminated |= !winners.contains(&entrant.owner);
+                }
+                self.state.entrants.set(entrants);
+
+                if winners.len() <= 1 {
+                    self.state.status.set(TournamentStatus::Finished);
+                } else {
+                    self.open_round(winners);
+                }
+            }
+            TournamentFormat::Swiss => {
+                if round >= config.swiss_rounds {
+                    self.state.status.set(TournamentStatus::Finished);
+                    return;
+                }
+
+                // Pair entrants with others on the same score, best first
+                entrants.sort_by_key(|e| (std::cmp::Reverse(e.points), e.seed));
+                let standings = entrants.iter().map(|e| e.owner).collect();
+                self.open_round(standings);
+            }
+        }
+    }
+
+    /// Seat `order` at new tables and instantiate a Linot match for each
+    fn open_round(&mut self, order: Vec<AccountOwner>) {
+        let config = self.state.config.get().clone();
+        let round = *self.state.round.get() + 1;
+        let TournamentParameters { linot_module } = self.runtime.application_parameters();
+
+        let sizes = pairing::table_sizes(order.len(), config.table_size as usize);
+        let seating = match config.format {
+            TournamentFormat::SingleElimination => pairing::snake_seating(&order, &sizes),
+            TournamentFormat::Swiss => pairing::group_seating(&order, &sizes),
+        };
+
+        let mut tables = self.state.tables.get().clone();
+        for seats in seating {
+            // Play-out, so every seat gets a placement to score; every seat
+            // is held for the entrant drawn for it, so neither strangers nor
+            // bots can take it; starts by itself once they are all seated and
+            // ready, and expires if someone never shows up
+            let reserved_seats = seats
+                .iter()
+                .enumerate()
+                .map(|(seat, &owner)| SeatReservation {
+                    seat: seat as u8,
+                    owner,
+                })
+                .collect();
+            let match_config = MatchConfig {
+                max_players: seats.len() as u8,
+                host: config.organizer,
+                is_ranked: config.is_ranked,
+                play_out: true,
+                allowlist: seats.clone(),
+                reserved_seats,
+                auto_start: true,
+                lobby_expiry_secs: Some(config.table_lobby_secs),
+                ..MatchConfig::default()
+            };
+            let application_id: ApplicationId<LinotAbi> = self.runtime.create_application(
+                linot_module,
+                &(),
+                &match_config,
+                Vec::new(),
+            );
+
+            // Hear about the result as soon as the match ends
+            let chain_id = self.runtime.chain_id();
+            let results = StreamName(RESULTS_STREAM.to_vec());
+            self.runtime.subscribe_to_events(chain_id, application_id.forget_abi(), results);
+
+            tables.push(Table {
+                round,
+                application_id: application_id.forget_abi(),
+                seats,
+                result: None,
+            });
+        }
+
+        self.state.tables.set(tables);
+        self.state.round.set(round);
+    }
+}
@@ -0,0 +1,135 @@
+use async_graphql::{Request, Response};
+use linera_sdk::{
+    abi::{ContractAbi, ServiceAbi},
+    linera_base_types::{AccountOwner, ModuleId},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// ============ Error Types ============
+
+#[derive(Debug, Error)]
+pub enum TournamentError {
+    #[error("Registration is closed")]
+    RegistrationClosed,
+
+    #[error("Tournament is full (max {0} entrants)")]
+    TournamentFull(u32),
+
+    #[error("Already registered")]
+    AlreadyRegistered,
+
+    #[error("Invalid nickname: {0}")]
+    InvalidNickname(#[from] linot::NicknameError),
+
+    #[error("Not registered")]
+    NotRegistered,
+
+    #[error("Only the organizer can start the tournament")]
+    OnlyOrganizerCanStart,
+
+    #[error("Need at least {0} entrants to start")]
+    NotEnoughEntrants(usize),
+
+    #[error("Tables must seat 2 to 6 players, got {0}")]
+    InvalidTableSize(u8),
+
+    #[error("Tournament not in progress")]
+    TournamentNotInProgress,
+
+    #[error("Caller authentication required")]
+    CallerRequired,
+}
+
+// ============ ABI Definition ============
+
+pub struct TournamentAbi;
+
+impl ContractAbi for TournamentAbi {
+    type Operation = Operation;
+    type Response = ();
+}
+
+impl ServiceAbi for TournamentAbi {
+    type Query = Request;
+    type QueryResponse = Response;
+}
+
+/// Application parameters, fixed when the tournament application is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentParameters {
+    /// Published Linot module used to instantiate one match table per pairing
+    pub linot_module: ModuleId,
+}
+
+// ============ Tournament Configuration ============
+
+/// How entrants progress from round to round
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
+pub enum TournamentFormat {
+    /// Only each table's winner advances, until one entrant remains
+    SingleElimination,
+    /// Everyone plays every round, paired by points with others on the same score
+    Swiss,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct TournamentConfig {
+    /// Display name of the event
+    pub name: String,
+    /// Bracket or Swiss
+    pub format: TournamentFormat,
+    /// Number of rounds played in Swiss format
+    pub swiss_rounds: u32,
+    /// Preferred players per table (2 to 6)
+    pub table_size: u8,
+    /// Maximum number of entrants
+    pub max_entrants: u32,
+    /// Organizer account (defaults to the creator)
+    pub organizer: Option<AccountOwner>,
+    /// Whether the tables are ranked matches
+    pub is_ranked: bool,
+    /// How long a round table waits for its entrants; entrants still missing
+    /// then forfeit and place behind everyone who took their seat
+    #[serde(default = "default_table_lobby_secs")]
+    pub table_lobby_secs: u64,
+}
+
+fn default_table_lobby_secs() -> u64 {
+    600
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            format: TournamentFormat::SingleElimination,
+            swiss_rounds: 3,
+            table_size: 2,
+            max_entrants: 64,
+            organizer: None,
+            is_ranked: false,
+            table_lobby_secs: default_table_lobby_secs(),
+        }
+    }
+}
+
+// ============ Operations (GraphQL Mutations) ============
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Operation {
+    /// Register for the tournament
+    Register { nickname: String },
+
+    /// Withdraw before the tournament starts
+    Withdraw,
+
+    /// Close registration, seed the first round and open its tables (organizer only)
+    StartTournament,
+
+    /// Read finished tables and advance to the next round once all are done
+    ///
+    /// Finished matches are scored from their result stream; this is for
+    /// tables that never announce one (an expired lobby, a cancelled match).
+    CollectResults,
+}
@@ -0,0 +1,135 @@
+//! Round pairing: splitting entrants into tables and scoring placements
+//!
+//! Pure functions shared by the contract (to seat each round) and the
+//! service (to preview standings).
+
+/// Largest table a Linot match supports
+pub const MAX_TABLE_SIZE: usize = 6;
+
+/// Split `count` entrants into tables close to `table_size`, as evenly as possible
+///
+/// Leftover entrants join existing tables rather than sitting out, so every
+/// table seats at least two players and no more than `MAX_TABLE_SIZE`.
+pub fn table_sizes(count: usize, table_size: usize) -> Vec<usize> {
+    if count < 2 || table_size < 2 {
+        return Vec::new();
+    }
+
+    let mut tables = (count / table_size).max(1);
+    if count.div_ceil(tables) > MAX_TABLE_SIZE {
+        tables = count.div_ceil(table_size);
+    }
+
+    let base = count / tables;
+    let extra = count % tables;
+    (0..tables).map(|table| base + usize::from(table < extra)).collect()
+}
+
+/// Seat entrants given in seed order so the top seeds start at different tables
+///
+/// Seeds are dealt across the tables in a snake (1, 2, 3, 3, 2, 1, ...),
+/// skipping tables that are already full.
+pub fn snake_seating<T: Clone>(order: &[T], sizes: &[usize]) -> Vec<Vec<T>> {
+    let mut tables: Vec<Vec<T>> = sizes.iter().map(|&size| Vec::with_capacity(size)).collect();
+    let mut lane: Vec<usize> = (0..sizes.len()).collect();
+    let mut entrants = order.iter();
+
+    'deal: loop {
+        for &table in &lane {
+            if tables[table].len() == sizes[table] {
+                continue;
+            }
+            match entrants.next() {
+                Some(entrant) => tables[table].push(entrant.clone()),
+                None => break 'deal,
+            }
+        }
+        if tables.iter().zip(sizes).all(|(seated, &size)| seated.len() == size) {
+            break;
+        }
+        lane.reverse();
+    }
+
+    tables
+}
+
+/// Seat entrants given in standings order with their nearest neighbours (Swiss)
+pub fn group_seating<T: Clone>(order: &[T], sizes: &[usize]) -> Vec<Vec<T>> {
+    let mut rest = order;
+    sizes
+        .iter()
+        .map(|&size| {
+            let (table, tail) = rest.split_at(size.min(rest.len()));
+            rest = tail;
+            table.to_vec()
+        })
+        .collect()
+}
+
+/// Points for finishing at `place` (0 is first) at a table of `table_len` players
+///
+/// One point for every player finished ahead of.
+pub fn placement_points(place: usize, table_len: usize) -> u32 {
+    table_len.saturating_sub(place + 1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_sizes_spread_entrants_evenly() {
+        assert_eq!(table_sizes(8, 4), vec![4, 4]);
+        assert_eq!(table_sizes(10, 4), vec![5, 5]);
+        assert_eq!(table_sizes(9, 2), vec![3, 2, 2, 2]);
+    }
+
+    #[test]
+    fn table_sizes_never_leave_a_table_too_small_or_too_large() {
+        assert_eq!(table_sizes(3, 4), vec![3]);
+        assert_eq!(table_sizes(11, 2), vec![3, 2, 2, 2, 2]);
+        // Seven at tables of four: one table would seat seven, so open two
+        assert_eq!(table_sizes(7, 4), vec![4, 3]);
+        for count in 2..40 {
+            for table_size in 2..=MAX_TABLE_SIZE {
+                let sizes = table_sizes(count, table_size);
+                assert_eq!(sizes.iter().sum::<usize>(), count);
+                assert!(sizes.iter().all(|&size| (2..=MAX_TABLE_SIZE).contains(&size)));
+            }
+        }
+    }
+
+    #[test]
+    fn table_sizes_need_two_entrants() {
+        assert!(table_sizes(1, 4).is_empty());
+        assert!(table_sizes(0, 2).is_empty());
+    }
+
+    #[test]
+    fn snake_seating_spreads_the_top_seeds() {
+        let seeds: Vec<u32> = (1..=8).collect();
+        let tables = snake_seating(&seeds, &[4, 4]);
+        assert_eq!(tables, vec![vec![1, 4, 5, 8], vec![2, 3, 6, 7]]);
+    }
+
+    #[test]
+    fn snake_seating_skips_full_tables() {
+        let seeds: Vec<u32> = (1..=7).collect();
+        let tables = snake_seating(&seeds, &[4, 3]);
+        assert_eq!(tables, vec![vec![1, 4, 5, 7], vec![2, 3, 6]]);
+    }
+
+    #[test]
+    fn group_seating_keeps_neighbours_together() {
+        let standings: Vec<u32> = (1..=7).collect();
+        let tables = group_seating(&standings, &[4, 3]);
+        assert_eq!(tables, vec![vec![1, 2, 3, 4], vec![5, 6, 7]]);
+    }
+
+    #[test]
+    fn placement_points_count_players_finished_ahead_of() {
+        assert_eq!(placement_points(0, 4), 3);
+        assert_eq!(placement_points(3, 4), 0);
+        assert_eq!(placement_points(5, 4), 0);
+    }
+}
@@ -0,0 +1,152 @@
+#![cfg_attr(target_arch = "wasm32", no_main)]
+
+#[allow(dead_code)] // Shared with the contract; the service only needs the scoring
+mod pairing;
+mod state;
+
+use async_graphql::{Context, EmptySubscription, Object, Request, Response, Schema};
+use linera_sdk::{
+    abi::WithServiceAbi,
+    linera_base_types::{AccountOwner, ApplicationId},
+    views::View,
+    Service, ServiceRuntime,
+};
+use std::sync::Arc;
+
+use crate::state::{Entrant, Table, TournamentState, TournamentStatus};
+use linot_tournament::{Operation, TournamentAbi, TournamentConfig, TournamentParameters};
+
+/// GraphQL service for querying tournament state
+pub struct TournamentService {
+    state: Arc<TournamentState>,
+    runtime: Arc<ServiceRuntime<Self>>,
+}
+
+linera_sdk::service!(TournamentService);
+
+impl WithServiceAbi for TournamentService {
+    type Abi = TournamentAbi;
+}
+
+impl Service for TournamentService {
+    type Parameters = TournamentParameters;
+
+    async fn new(runtime: ServiceRuntime<Self>) -> Self {
+        let state = TournamentState::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load state");
+        TournamentService {
+            state: Arc::new(state),
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    async fn handle_query(&self, request: Request) -> Response {
+        let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .data(self.state.clone())
+            .data(self.runtime.clone())
+            .finish();
+
+        schema.execute(request).await
+    }
+}
+
+/// GraphQL query root
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Get the tournament configuration
+    async fn config(&self, ctx: &Context<'_>) -> TournamentConfig {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        state.config.get().clone()
+    }
+
+    /// Get the tournament status
+    async fn status(&self, ctx: &Context<'_>) -> TournamentStatus {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        *state.status.get()
+    }
+
+    /// Get the current round number (0 before the start)
+    async fn round(&self, ctx: &Context<'_>) -> u32 {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        *state.round.get()
+    }
+
+    /// Get the entrants in seed order
+    async fn entrants(&self, ctx: &Context<'_>) -> Vec<Entrant> {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        state.entrants.get().clone()
+    }
+
+    /// Get the standings: entrants still in contention first, then by points and seed
+    async fn standings(&self, ctx: &Context<'_>) -> Vec<Entrant> {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        let mut entrants = state.entrants.get().clone();
+        entrants.sort_by_key(|e| (e.eliminated, std::cmp::Reverse(e.points), e.seed));
+        entrants
+    }
+
+    /// Get the tables of a round (the current round by default)
+    async fn tables(&self, ctx: &Context<'_>, round: Option<u32>) -> Vec<Table> {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        let round = round.unwrap_or(*state.round.get());
+        state
+            .tables
+            .get()
+            .iter()
+            .filter(|table| table.round == round)
+            .cloned()
+            .collect()
+    }
+
+    /// Get the Linot application an entrant should play at this round, if any
+    async fn my_table(&self, ctx: &Context<'_>, owner: AccountOwner) -> Option<ApplicationId> {
+        let state = ctx.data_unchecked::<Arc<TournamentState>>();
+        let round = *state.round.get();
+        state
+            .tables
+            .get()
+            .iter()
+            .find(|table| table.round == round && table.seats.contains(&owner))
+            .map(|table| table.application_id)
+    }
+}
+
+// ============ GraphQL Mutation Root ============
+
+/// GraphQL mutation root for tournament actions
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Register for the tournament with a nickname
+    async fn register(&self, ctx: &Context<'_>, nickname: String) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<TournamentService>>>();
+        runtime.schedule_operation(&Operation::Register { nickname });
+        true
+    }
+
+    /// Withdraw before the tournament starts
+    async fn withdraw(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<TournamentService>>>();
+        runtime.schedule_operation(&Operation::Withdraw);
+        true
+    }
+
+    /// Close registration and open the first round (organizer only)
+    async fn start_tournament(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<TournamentService>>>();
+        runtime.schedule_operation(&Operation::StartTournament);
+        true
+    }
+
+    /// Collect tables that ended without announcing a result (expired or
+    /// cancelled) and advance the round when all are done
+    async fn collect_results(&self, ctx: &Context<'_>) -> bool {
+        let runtime = ctx.data_unchecked::<Arc<ServiceRuntime<TournamentService>>>();
+        runtime.schedule_operation(&Operation::CollectResults);
+        true
+    }
+}
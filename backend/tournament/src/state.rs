@@ -0,0 +1,66 @@
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ApplicationId},
+    views::{RegisterView, RootView, ViewStorageContext},
+};
+use serde::{Deserialize, Serialize};
+
+use linot_tournament::TournamentConfig;
+
+/// Root application state stored on-chain using Linera Views
+#[derive(RootView)]
+#[view(context = ViewStorageContext)]
+pub struct TournamentState {
+    /// Tournament configuration (set at instantiation)
+    pub config: RegisterView<TournamentConfig>,
+    /// Registration, play or finished
+    pub status: RegisterView<TournamentStatus>,
+    /// Registered entrants, in seed order
+    pub entrants: RegisterView<Vec<Entrant>>,
+    /// Current round number (1-based, 0 before the start)
+    pub round: RegisterView<u32>,
+    /// Match tables of every round played so far
+    pub tables: RegisterView<Vec<Table>>,
+}
+
+// ============ Tournament Status ============
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, async_graphql::Enum)]
+pub enum TournamentStatus {
+    /// Accepting entrants
+    #[default]
+    Registration,
+    /// Rounds are being played
+    InProgress,
+    /// Final standings are known
+    Finished,
+}
+
+// ============ Entrants ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct Entrant {
+    /// Owner account
+    pub owner: AccountOwner,
+    /// Display nickname
+    pub nickname: String,
+    /// Seed (registration order, 1 is the top seed)
+    pub seed: u32,
+    /// Points earned from table placements
+    pub points: u32,
+    /// Knocked out of a single-elimination bracket
+    pub eliminated: bool,
+}
+
+// ============ Tables ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct Table {
+    /// Round this table belongs to
+    pub round: u32,
+    /// Linot application instance hosting the match
+    pub application_id: ApplicationId,
+    /// Entrants assigned to the table
+    pub seats: Vec<AccountOwner>,
+    /// Entrants in finishing order, 1st first (once the match finished)
+    pub result: Option<Vec<AccountOwner>>,
+}
@@ -3,6 +3,8 @@
  * Wrapper for querying the Linot card game application via GraphQL
  */
 
+import type { Card as LocalCard } from "../types/game";
+
 interface GameState {
   status: string;
   currentPlayerIndex: number | null;
@@ -20,6 +22,40 @@ interface MatchConfig {
   cardsPerPlayer: number;
 }
 
+/**
+ * Result of a move mutation: the service checks the move before scheduling it
+ */
+interface MoveOutcome {
+  scheduled: boolean;
+  turnVerified: boolean;
+  error: string | null;
+  errorCode: string | null;
+}
+
+// Suit order of the contract's unshuffled deck, which defines stable card IDs
+const DECK_SUITS = ["circle", "cross", "triangle", "square", "star"];
+
+/**
+ * Stable contract card ID for a local card: its position in the unshuffled
+ * deck (14 values per suit, then the 5 Whot cards)
+ */
+export const contractCardId = (card: LocalCard): number => {
+  if (card.suit === "whot") {
+    return 70 + (Number(card.id.split("-")[1]) - 1 || 0);
+  }
+  return DECK_SUITS.indexOf(card.suit) * 14 + (card.number - 1);
+};
+
+/**
+ * Throw the contract's reason when a mutation was not scheduled
+ */
+const expectScheduled = (outcome: MoveOutcome): MoveOutcome => {
+  if (!outcome.scheduled) {
+    throw new Error(outcome.error ?? "Operation rejected");
+  }
+  return outcome;
+};
+
 class LineraClient {
   private graphqlUrl: string;
 
@@ -106,15 +142,15 @@ class LineraClient {
   /**
    * Join the match with a nickname
    */
-  async joinMatch(nickname: string): Promise<boolean> {
+  async joinMatch(nickname: string): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        joinMatch(nickname: "${nickname}")
+        joinMatch(nickname: "${nickname}") { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ joinMatch: boolean }>(mutation);
-      return data.joinMatch;
+      const data = await this.query<{ joinMatch: MoveOutcome }>(mutation);
+      return expectScheduled(data.joinMatch);
     } catch (error) {
       console.error("Failed to join match:", error);
       throw error;
@@ -124,15 +160,15 @@ class LineraClient {
   /**
    * Start a new match (host only)
    */
-  async startMatch(): Promise<boolean> {
+  async startMatch(): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        startMatch
+        startMatch { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ startMatch: boolean }>(mutation);
-      return data.startMatch;
+      const data = await this.query<{ startMatch: MoveOutcome }>(mutation);
+      return expectScheduled(data.startMatch);
     } catch (error) {
       console.error("Failed to start match:", error);
       throw error;
@@ -140,36 +176,55 @@ class LineraClient {
   }
 
   /**
-   * Play a card from your hand
+   * Play a card from your hand, by its stable card ID
    */
-  async playCard(cardIndex: number, chosenSuit?: string): Promise<boolean> {
-    const suitParam = chosenSuit ? `, chosenSuit: "${chosenSuit}"` : "";
+  async playCard(cardId: number, chosenSuit?: string): Promise<MoveOutcome> {
+    // chosenSuit is a GraphQL enum (CIRCLE, CROSS, TRIANGLE, SQUARE, STAR)
+    const suitParam = chosenSuit ? `, chosenSuit: ${chosenSuit.toUpperCase()}` : "";
     const mutation = `
       mutation {
-        playCard(cardIndex: ${cardIndex}${suitParam})
+        playCard(cardId: ${cardId}${suitParam}) { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ playCard: boolean }>(mutation);
-      return data.playCard;
+      const data = await this.query<{ playCard: MoveOutcome }>(mutation);
+      return expectScheduled(data.playCard);
     } catch (error) {
       console.error("Failed to play card:", error);
       throw error;
     }
   }
 
+  /**
+   * Choose the demanded suit after playing a Whot card without one
+   */
+  async chooseSuit(suit: string): Promise<MoveOutcome> {
+    const mutation = `
+      mutation {
+        chooseSuit(suit: ${suit.toUpperCase()}) { scheduled turnVerified error errorCode }
+      }
+    `;
+    try {
+      const data = await this.query<{ chooseSuit: MoveOutcome }>(mutation);
+      return expectScheduled(data.chooseSuit);
+    } catch (error) {
+      console.error("Failed to choose suit:", error);
+      throw error;
+    }
+  }
+
   /**
    * Draw a card from the deck
    */
-  async drawCard(): Promise<boolean> {
+  async drawCard(): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        drawCard
+        drawCard { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ drawCard: boolean }>(mutation);
-      return data.drawCard;
+      const data = await this.query<{ drawCard: MoveOutcome }>(mutation);
+      return expectScheduled(data.drawCard);
     } catch (error) {
       console.error("Failed to draw card:", error);
       throw error;
@@ -179,15 +234,15 @@ class LineraClient {
   /**
    * Call "Last Card" when you have one card remaining
    */
-  async callLastCard(): Promise<boolean> {
+  async callLastCard(): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        callLastCard
+        callLastCard { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ callLastCard: boolean }>(mutation);
-      return data.callLastCard;
+      const data = await this.query<{ callLastCard: MoveOutcome }>(mutation);
+      return expectScheduled(data.callLastCard);
     } catch (error) {
       console.error("Failed to call last card:", error);
       throw error;
@@ -197,15 +252,15 @@ class LineraClient {
   /**
    * Challenge another player for not calling "Last Card"
    */
-  async challengeLastCard(playerIndex: number): Promise<boolean> {
+  async challengeLastCard(playerIndex: number): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        challengeLastCard(playerIndex: ${playerIndex})
+        challengeLastCard(playerIndex: ${playerIndex}) { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ challengeLastCard: boolean }>(mutation);
-      return data.challengeLastCard;
+      const data = await this.query<{ challengeLastCard: MoveOutcome }>(mutation);
+      return expectScheduled(data.challengeLastCard);
     } catch (error) {
       console.error("Failed to challenge last card:", error);
       throw error;
@@ -215,15 +270,15 @@ class LineraClient {
   /**
    * Leave the current match
    */
-  async leaveMatch(): Promise<boolean> {
+  async leaveMatch(): Promise<MoveOutcome> {
     const mutation = `
       mutation {
-        leaveMatch
+        leaveMatch { scheduled turnVerified error errorCode }
       }
     `;
     try {
-      const data = await this.query<{ leaveMatch: boolean }>(mutation);
-      return data.leaveMatch;
+      const data = await this.query<{ leaveMatch: MoveOutcome }>(mutation);
+      return expectScheduled(data.leaveMatch);
     } catch (error) {
       console.error("Failed to leave match:", error);
       throw error;
@@ -235,4 +290,4 @@ class LineraClient {
 export const lineraClient = new LineraClient();
 
 // Export types
-export type { GameState, Card, MatchConfig };
+export type { GameState, Card, MatchConfig, MoveOutcome };
@@ -17,7 +17,7 @@ interface BlockchainGameStore {
   fetchGameState: () => Promise<void>;
   joinMatch: (nickname: string) => Promise<void>;
   startMatch: () => Promise<void>;
-  playCard: (cardIndex: number, chosenSuit?: string) => Promise<void>;
+  playCard: (cardId: number, chosenSuit?: string) => Promise<void>;
   drawCard: () => Promise<void>;
   callLastCard: () => Promise<void>;
   startPolling: () => void;
@@ -104,11 +104,11 @@ export const useBlockchainGameStore = create<BlockchainGameStore>(
     },
 
     // Play a card
-    playCard: async (cardIndex: number, chosenSuit?: string) => {
+    playCard: async (cardId: number, chosenSuit?: string) => {
       try {
         set({ isLoading: true, error: null });
 
-        await lineraClient.playCard(cardIndex, chosenSuit);
+        await lineraClient.playCard(cardId, chosenSuit);
 
         // Wait for blockchain to process
         await new Promise((resolve) => setTimeout(resolve, 1000));
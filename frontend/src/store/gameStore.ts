@@ -14,7 +14,7 @@ import {
   reshuffleDiscardPile,
 } from "../lib/gameLogic";
 import toast from "react-hot-toast";
-import { lineraClient } from "../lib/lineraClient";
+import { contractCardId, lineraClient } from "../lib/lineraClient";
 
 interface GameStore extends GameState {
   // Actions
@@ -166,7 +166,7 @@ export const useGameStore = create<GameStore>((set, get) => ({
       if (playerId === "player") {
         try {
           console.log("🔗 Logging winning move to blockchain...");
-          await lineraClient.playCard(cardIndex);
+          await lineraClient.playCard(contractCardId(card));
         } catch (error) {
           console.warn("⚠️ Blockchain sync failed:", error);
         }
@@ -410,7 +410,7 @@ export const useGameStore = create<GameStore>((set, get) => ({
         console.log(
           `🔗 Syncing card play to blockchain: ${card.suit} ${card.number}`
         );
-        await lineraClient.playCard(cardIndex);
+        await lineraClient.playCard(contractCardId(card));
         console.log("✅ Card play synced to blockchain");
       } catch (error) {
         console.warn("⚠️ Blockchain sync failed (continuing locally):", error);